@@ -1,140 +1,374 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
 use std::path::Path;
 
+use aoc_common::{attr::AocEntry, Part};
 use glob::glob;
-use regex::Regex;
-use syn::parse_quote;
+use proc_macro2::Span;
+use syn::{parse_quote, Item};
+
+/// Directory of example inputs, relative to this crate.
+const EXAMPLES_DIR: &str = "../data/examples";
+
+/// A solution found in `src/solutions`.
+struct Solution {
+    year_mod: String,
+    day_mod: String,
+    entry: AocEntry,
+    /// Where the solution's function is declared, as `file:line:column`.
+    location: String,
+}
+
+impl Solution {
+    /// Description of the solution for error messages.
+    fn describe(&self) -> String {
+        format!(
+            "{}/{:02} part {}{}",
+            self.entry.year,
+            self.entry.day,
+            self.entry.part,
+            self.entry.version.as_ref().map(|v| format!(r#" (version "{v}")"#)).unwrap_or_default(),
+        )
+    }
+}
+
+/// Location of `span` within `file`, formatted as `file:line:column`.
+fn location(file: &Path, span: Span) -> String {
+    let start = span.start();
+    format!("{}:{}:{}", file.display(), start.line, start.column + 1)
+}
+
+/// Whether `attr` is the `aoc` attribute, however its path is written.
+fn is_aoc_attr(attr: &syn::Attribute) -> bool {
+    attr.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "aoc")
+}
+
+/// Find the arguments of every `aoc` attribute in `items`, adding an error
+/// for any attribute that can't be parsed, or that isn't on a top-level
+/// function (which the generated registration couldn't refer to).
+fn find_entries(file: &Path, items: &[Item], top_level: bool, entries: &mut Vec<(AocEntry, String)>, errors: &mut Vec<String>) {
+    for item in items {
+        match item {
+            Item::Fn(func) => {
+                for attr in func.attrs.iter().filter(|attr| is_aoc_attr(attr)) {
+                    if !top_level {
+                        errors.push(format!(
+                            "{}: solution `{}` must be declared at the top level of its module",
+                            location(file, func.sig.ident.span()),
+                            func.sig.ident,
+                        ));
+                        continue;
+                    }
+
+                    match attr.parse_args::<AocEntry>() {
+                        Ok(entry) => entries.push((entry, location(file, func.sig.ident.span()))),
+                        Err(e) => errors.extend(e
+                            .into_iter()
+                            .map(|e| format!("{}: {e}", location(file, e.span())))
+                        ),
+                    }
+                }
+            },
+            Item::Mod(syn::ItemMod { content: Some((_, items)), .. }) => {
+                find_entries(file, items, false, entries, errors);
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Find every solution in `file`, which is `src/solutions/<year>/<day>.rs`.
+fn find_solutions(file: &Path, solutions: &mut Vec<Solution>, errors: &mut Vec<String>) -> eyre::Result<()> {
+    let contents = fs::read_to_string(file)?;
+    let parsed = match syn::parse_file(&contents) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            errors.push(format!("{}: {e}", location(file, e.span())));
+            return Ok(());
+        },
+    };
+
+    let mut entries = Vec::new();
+    find_entries(file, &parsed.items, true, &mut entries, errors);
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let module_name = |path: Option<&std::ffi::OsStr>| {
+        path.and_then(|name| name.to_str())
+            .filter(|name| syn::parse_str::<syn::Ident>(name).is_ok())
+            .map(String::from)
+    };
+    let in_year_dir = file
+        .parent()
+        .and_then(Path::parent)
+        .is_some_and(|dir| dir == Path::new("src/solutions"));
+    let (true, Some(year_mod), Some(day_mod)) = (
+        in_year_dir,
+        module_name(file.parent().and_then(Path::file_name)),
+        module_name(file.file_stem()),
+    ) else {
+        errors.push(format!("{}: solutions must be in a file named `src/solutions/<year>/<day>.rs`, with both names valid module names", file.display()));
+        return Ok(());
+    };
+
+    solutions.extend(entries
+        .into_iter()
+        .map(|(entry, location)| Solution {
+            year_mod: year_mod.clone(),
+            day_mod: day_mod.clone(),
+            entry,
+            location,
+        })
+    );
+
+    Ok(())
+}
+
+/// Example input read from a file in [EXAMPLES_DIR].
+struct ExampleFile {
+    year: usize,
+    day: usize,
+    part: Part,
+    /// Path of the input, relative to the workspace root.
+    name: String,
+    /// Path of the input, relative to `src/register.rs`.
+    include_path: String,
+    expected: String,
+}
+
+/// Find every example in `data/examples/y<year>/day<day>/<name>.txt`, with
+/// the answers for either or both parts in `<name>.toml`:
+///
+/// ```toml
+/// A = 4
+/// B = "3"
+/// ```
+fn find_example_files(errors: &mut Vec<String>) -> eyre::Result<Vec<ExampleFile>> {
+    let mut examples = Vec::new();
+
+    for file in glob(&format!("{EXAMPLES_DIR}/*/*/*.txt")).expect("failed to read glob pattern") {
+        let file = file?;
+        let name = file
+            .strip_prefix("..")
+            .unwrap_or(&file)
+            .display()
+            .to_string();
+        let dir_number = |dir: Option<&Path>, prefix: &str| dir
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(prefix))
+            .and_then(|n| n.parse::<usize>().ok());
+        let (Some(year), Some(day)) = (
+            dir_number(file.parent().and_then(Path::parent), "y"),
+            dir_number(file.parent(), "day"),
+        ) else {
+            errors.push(format!("{name}: examples must be in a file named `data/examples/y<year>/day<day>/<name>.txt`"));
+            continue;
+        };
+
+        let answers_file = file.with_extension("toml");
+        let answers = match fs::read_to_string(&answers_file) {
+            Ok(answers) => answers,
+            Err(e) => {
+                errors.push(format!("{name}: unable to read answers from {}: {e}", answers_file.display()));
+                continue;
+            },
+        };
+        let answers: toml::Table = match answers.parse() {
+            Ok(answers) => answers,
+            Err(e) => {
+                errors.push(format!("{}: {e}", answers_file.display()));
+                continue;
+            },
+        };
+
+        for (part, expected) in answers {
+            let Ok(part) = part.parse::<Part>() else {
+                errors.push(format!("{}: expected answers for parts `A` or `B`, found `{part}`", answers_file.display()));
+                continue;
+            };
+            let expected = match expected {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(n) => n.to_string(),
+                v => {
+                    errors.push(format!("{}: expected string or integer answer for part {part}, found {}", answers_file.display(), v.type_str()));
+                    continue;
+                },
+            };
+
+            examples.push(ExampleFile {
+                year,
+                day,
+                part,
+                name: name.clone(),
+                include_path: format!("../{}", file.display()),
+                expected,
+            });
+        }
+    }
+
+    Ok(examples)
+}
+
+/// Add an error for every solution that has the same year, day, part, and
+/// version as an earlier one, or whose runner would have the same name as an
+/// earlier one's (such as versions `"my alt"` and `"my_alt"`).
+fn check_duplicates(solutions: &[Solution], errors: &mut Vec<String>) {
+    let mut keys = BTreeMap::new();
+    let mut runners = BTreeMap::new();
+
+    for solution in solutions {
+        let AocEntry { year, day, part, ref version, .. } = solution.entry;
+
+        if let Some(first) = keys.insert((year, day, part, version.clone()), solution) {
+            errors.push(format!(
+                "{}: duplicate solution for {}, first declared at {}",
+                solution.location,
+                solution.describe(),
+                first.location,
+            ));
+            continue;
+        }
+
+        let runner_name = (&solution.year_mod, &solution.day_mod, solution.entry.runner_name());
+        if let Some(first) = runners.insert(runner_name, solution) {
+            errors.push(format!(
+                "{}: runner for {} has the same name, `{}`, as the runner for {} declared at {}",
+                solution.location,
+                solution.describe(),
+                solution.entry.runner_name(),
+                first.describe(),
+                first.location,
+            ));
+        }
+    }
+}
 
 fn main() -> eyre::Result<()> {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=src/solutions");
+    println!("cargo:rerun-if-changed={EXAMPLES_DIR}");
+
+    let mut solutions = Vec::new();
+    let mut errors = Vec::new();
+
+    for file in glob("src/solutions/**/*.rs").expect("failed to read glob pattern") {
+        find_solutions(&file?, &mut solutions, &mut errors)?;
+    }
+
+    check_duplicates(&solutions, &mut errors);
+    let example_files = find_example_files(&mut errors)?;
+
+    if !errors.is_empty() {
+        eyre::bail!("unable to register solutions:\n{}", errors.join("\n"));
+    }
 
-    let attr_re = Regex::new(r#"aoc\((.+)\)\]"#).unwrap();
-    let kv_re = Regex::new(r#"(?P<key>(year|day|part|version|separator))\s*=\s*\"?(?P<value>[a-zA-Z0-9_\s\\]+)\"?"#).unwrap();
     let mut refs = Vec::new();
-    let mut modules = HashMap::new();
-
-    for entry in glob("src/solutions/**/*.rs").expect("failed to read glob pattern") {
-        if let Ok(file) = entry {
-            for l in BufReader::new(File::open(file.clone())?).lines() {
-                let l = l?;
-                if let Some(m) = attr_re.captures(&l) {
-                    let attr = m.get(1).unwrap().as_str();
-                    let mut kv: HashMap<&str, &str> = HashMap::new();
-
-                    for m in kv_re.captures_iter(attr) {
-                        let key = m.name("key").unwrap().as_str();
-                        let value = m.name("value").unwrap().as_str();
-                        let entry = kv.entry(key).or_insert(value);
-                        *entry = value;
-                    }
+    let mut tests: Vec<syn::ItemFn> = Vec::new();
+    let mut modules: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
 
-                    if !kv.contains_key("year") || !kv.contains_key("day") || !kv.contains_key("part") {
-                        eprintln!("cannot register runner without setting year, day, or part");
-                        continue;
-                    }
+    for Solution { year_mod, day_mod, entry, .. } in solutions {
+        let AocEntry { year, day, part, ref version, .. } = entry;
+        let part = part.to_string();
+        let version: syn::Expr = match version {
+            Some(v) => parse_quote!(Some(String::from(#v))),
+            None => parse_quote!(None),
+        };
+        let year_mod_name: syn::Ident = syn::parse_str(&year_mod)?;
+        let mod_name: syn::Ident = syn::parse_str(&day_mod)?;
+        let runner_name: syn::Ident = syn::parse_str(&entry.runner_name())?;
+        let stmt: syn::Stmt = parse_quote! {
+            crate::register_runner(#year, #day, #part, #version, crate::solutions::#year_mod_name::#mod_name::#runner_name);
+        };
+        refs.push(stmt);
 
-                    let year: usize = kv["year"].parse()?;
-                    let day: usize = kv["day"].parse()?;
-                    let part = kv["part"];
-                    let version = kv
-                        .get("version")
-                        .map(|v| *v);
-                    let (version, extension): (syn::Expr, &str) = match version {
-                        Some(v) => (parse_quote!(Some(String::from(#v))), v),
-                        None => (parse_quote!(None), "none"),
-                    };
-                    let year_mod_name: syn::Ident = syn::parse_str(&file
-                        .parent()
-                        .unwrap()
-                        .file_name()
-                        .unwrap()
-                        .to_owned()
-                        .into_string()
-                        .unwrap()
-                    )?;
-                    let mod_name: syn::Ident = syn::parse_str(file
-                        .file_name()
-                        .unwrap()
-                        .to_owned()
-                        .into_string()
-                        .unwrap()
-                        .strip_suffix(".rs")
-                        .unwrap()
-                    )?;
-                    let runner_name: syn::Ident =
-                        syn::parse_str(&format!("runner_y{year}_day{day:02}_part{part}_{extension}"))?;
-                    let stmt: syn::Stmt = parse_quote! {
-                        crate::register_runner(#year, #day, #part, #version, crate::solutions::#year_mod_name::#mod_name::#runner_name);
-                    };
-                    refs.push(stmt);
-
-                    let entry = modules
-                        .entry(year_mod_name)
-                        .or_insert_with(|| HashSet::new());
-                    entry.insert(mod_name);
+        let attr_examples = entry.examples
+            .iter()
+            .enumerate()
+            .map(|(i, example)| {
+                let input = &example.input;
+                (format!("#{}", i + 1), quote::quote!(#input), &example.expected)
+            });
+        let file_examples = example_files
+            .iter()
+            .filter(|example| (example.year, example.day, example.part) == (year, day, entry.part))
+            .map(|example| {
+                let include_path = &example.include_path;
+                (example.name.clone(), quote::quote!(include_str!(#include_path)), &example.expected)
+            });
+
+        for (i, (name, input, expected)) in attr_examples.chain(file_examples).enumerate() {
+            refs.push(parse_quote! {
+                crate::register_example(#year, #day, #part, #version, #name, #input, #expected);
+            });
+
+            let test_name: syn::Ident = syn::parse_str(&format!("{runner_name}_example_{}", i + 1))?;
+            let (expected, message) = (expected.trim(), format!("example {name}"));
+            tests.push(parse_quote! {
+                #[test]
+                #[allow(non_snake_case)]
+                fn #test_name() {
+                    let answer = crate::input::with_input(
+                        #input.as_bytes(),
+                        || crate::solutions::#year_mod_name::#mod_name::#runner_name(&mut crate::Timings::default()),
+                    );
+                    assert_eq!(answer.unwrap().to_string().trim(), #expected, #message);
                 }
-            }
+            });
         }
+
+        modules
+            .entry(year_mod)
+            .or_default()
+            .insert(day_mod);
     }
 
-    let current_dir = std::env::current_dir().unwrap();
-    // TODO: Rewrite the way that years is written to file, so it can be sorted.
-    // Use common function to write collection of syn::Stmt to file?
     let mut years = Vec::new();
-    for (year, days) in modules.into_iter() {
-        let year_dir_name = format!(
-            "{}/src/solutions/{}",
-            current_dir.display(),
-            year.to_string(),
-        );
-        let year_dir = Path::new(&year_dir_name);
-        if !year_dir.exists() {
-            fs::create_dir_all(year_dir)?;
-        }
-
-        let mut days = days
-            .into_iter()
-            .collect::<Vec<_>>();
-        days.sort_by_cached_key(|i| i.to_string());
+    for (year, days) in modules {
+        let year_dir = Path::new("src/solutions").join(&year);
         let days: Vec<syn::Stmt> = days
-            .into_iter()
-            .map(|day| parse_quote! {
-                pub(crate) mod #day;
+            .iter()
+            .map(|day| {
+                let day: syn::Ident = syn::parse_str(day).unwrap();
+                parse_quote! {
+                    pub(crate) mod #day;
+                }
             })
             .collect();
 
-        let year_mod = year_dir.join("mod.rs");
         let days: syn::File = parse_quote! {
             #(#days)*
         };
-        fs::write(year_mod, &prettyplease::unparse(&days))?;
+        fs::write(year_dir.join("mod.rs"), prettyplease::unparse(&days))?;
 
+        let year: syn::Ident = syn::parse_str(&year)?;
         let year: syn::Stmt = parse_quote! {
             pub(crate) mod #year;
         };
         years.push(year);
     }
 
-    let solution_mod_name = format!(
-        "{}/src/solutions/mod.rs",
-        current_dir.display(),
-    );
-    let solution_mod = Path::new(&solution_mod_name);
     let years: syn::File = parse_quote! {
         #(#years)*
     };
-    fs::write(solution_mod, &prettyplease::unparse(&years))?;
+    fs::write("src/solutions/mod.rs", prettyplease::unparse(&years))?;
 
     let register: syn::File = parse_quote! {
         #[allow(unused_must_use)]
         pub fn register_runners() {
             #(#refs)*
         }
+
+        #[cfg(test)]
+        mod examples {
+            #(#tests)*
+        }
     };
-    fs::write("src/register.rs", &prettyplease::unparse(&register))?;
+    fs::write("src/register.rs", prettyplease::unparse(&register))?;
 
     Ok(())
 }
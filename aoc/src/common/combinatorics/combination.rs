@@ -1,8 +1,66 @@
+use super::{n_choose_k, size_hint};
+
+/// Number of ways to choose the rest of a combination of `k` of `n` items,
+/// from the `i`th index onwards, out of the indices from `from` up. `None`
+/// if it doesn't fit in a `u128`, which is more than any rank can be.
+fn count_from(n: usize, k: usize, i: usize, from: usize) -> Option<u128> {
+    n_choose_k(n - from, k - i)
+}
+
+/// Rank, in lexicographic order, of the combination of `indices.len()` of
+/// `n` items with the given strictly increasing indices. `None` if the rank
+/// doesn't fit in a `u128`.
+fn rank_indices(n: usize, indices: &[usize]) -> Option<u128> {
+    let k = indices.len();
+
+    // Counts the combinations that match up to index `i` and then have a
+    // lower value there, the reverse of unrank_indices.
+    let mut rank: u128 = 0;
+    let mut from = 0;
+
+    for (i, &c) in indices.iter().enumerate() {
+        for lower in from..c {
+            rank = rank.checked_add(count_from(n, k, i + 1, lower + 1)?)?;
+        }
+
+        from = c + 1;
+    }
+
+    Some(rank)
+}
+
+/// Fills `indices` with the combination of `indices.len()` of `n` items with
+/// rank `rank`, which must be in range.
+fn unrank_indices(n: usize, mut rank: u128, indices: &mut [usize]) {
+    let k = indices.len();
+    let mut from = 0;
+
+    for (i, index) in indices.iter_mut().enumerate() {
+        let mut c = from;
+
+        // Skips past every combination with a lower value at `i`.
+        while let Some(count) = count_from(n, k, i + 1, c + 1).filter(|&count| rank >= count) {
+            rank -= count;
+            c += 1;
+        }
+
+        *index = c;
+        from = c + 1;
+    }
+}
+
+/// Indices of `k` of `n` items, in lexicographic order. `rank` counts the
+/// combinations before the next one, and `end`, if set, is the rank to stop
+/// at.
+#[derive(Clone)]
 struct CombinationIndices {
     c: Vec<usize>,
     n: usize,
     k: usize,
     first: bool,
+    done: bool,
+    rank: u128,
+    end: Option<u128>,
 }
 
 impl CombinationIndices {
@@ -20,14 +78,54 @@ impl CombinationIndices {
             n,
             k,
             first: true,
+            done: false,
+            rank: 0,
+            end: None,
         }
     }
-}
 
-impl Iterator for CombinationIndices {
-    type Item = Vec<usize>;
+    /// Rank of the combination with the given indices, or `None` if they
+    /// aren't strictly increasing indices below `n`, or the rank overflows.
+    fn rank(&self, indices: &[usize]) -> Option<u128> {
+        if indices.len() != self.k || indices.windows(2).any(|w| w[0] >= w[1]) || indices[self.k - 1] >= self.n {
+            return None;
+        }
+
+        rank_indices(self.n, indices)
+    }
+
+    /// Moves to the combination with rank `rank`, so it's the next one
+    /// returned. Past the last combination, there are none left.
+    fn seek(&mut self, rank: u128) {
+        if n_choose_k(self.n, self.k).is_some_and(|total| rank >= total) {
+            self.done = true;
+            return;
+        }
+
+        self.rank = rank;
+        self.first = true;
+        self.done = false;
+        unrank_indices(self.n, rank, &mut self.c);
+    }
+
+    /// Number of combinations left to return, or `None` if it doesn't fit
+    /// in a `u128`.
+    fn remaining(&self) -> Option<u128> {
+        if self.done {
+            return Some(0);
+        }
+
+        let end = self.end.or_else(|| n_choose_k(self.n, self.k))?;
+        Some(end.saturating_sub(self.rank))
+    }
+
+    /// Advances to the next combination, and borrows its indices.
+    fn next_indices(&mut self) -> Option<&[usize]> {
+        if self.done || self.end.is_some_and(|end| self.rank >= end) {
+            self.done = true;
+            return None;
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
         if self.first {
             self.first = false;
         } else {
@@ -35,6 +133,7 @@ impl Iterator for CombinationIndices {
                 ref mut c,
                 ref n,
                 ref k,
+                ref mut done,
                 ..
             } = self;
 
@@ -44,6 +143,7 @@ impl Iterator for CombinationIndices {
             if c[i] > n - 1 {
                 while c[i] >= n - k + i {
                     if i == 0 {
+                        *done = true;
                         return None;
                     }
 
@@ -58,7 +158,8 @@ impl Iterator for CombinationIndices {
             }
         }
 
-        Some(self.c.clone())
+        self.rank += 1;
+        Some(&self.c)
     }
 }
 
@@ -67,21 +168,135 @@ pub struct Combinations<T> {
     indices: CombinationIndices,
 }
 
+impl<T> Combinations<T> {
+    /// Advances to the next combination, and borrows the indices of the
+    /// items in it, without cloning anything.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        self.indices.next_indices()
+    }
+
+    /// Calls `f` with each remaining combination, borrowing the items
+    /// rather than cloning them. Only allocates once, for the selection.
+    pub fn for_each_ref<F>(mut self, mut f: F)
+    where F: FnMut(&[&T])
+    {
+        let Self { data, indices } = &mut self;
+        let mut selection = Vec::with_capacity(indices.k);
+
+        while let Some(c) = indices.next_indices() {
+            selection.clear();
+            selection.extend(c.iter().map(|&i| &data[i]));
+            f(&selection);
+        }
+    }
+
+    /// Advances to the next combination, replacing the contents of `buffer`
+    /// with clones of its items. Returns false once there are none left.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool
+    where T: Clone
+    {
+        let Self { data, indices } = self;
+
+        match indices.next_indices() {
+            None => false,
+            Some(c) => {
+                buffer.clear();
+                buffer.extend(c.iter().map(|&i| data[i].clone()));
+                true
+            },
+        }
+    }
+
+    /// Position of the combination with the given item indices in the
+    /// lexicographic order this iterates in, counting from 0 for the first
+    /// `k` items. `None` if the indices aren't strictly increasing and in
+    /// range, or the rank doesn't fit in a `u128`.
+    pub fn rank(&self, indices: &[usize]) -> Option<u128> {
+        self.indices.rank(indices)
+    }
+
+    /// Number of combinations left, or `None` if it doesn't fit in a `u128`.
+    pub fn remaining(&self) -> Option<u128> {
+        self.indices.remaining()
+    }
+
+    /// Moves to the combination with the given rank, so it's the next one
+    /// returned, whichever have been returned already. Useful for resuming
+    /// a search from a checkpoint. Any end set by [split_at](Self::split_at)
+    /// still applies.
+    pub fn seek(&mut self, rank: u128) {
+        self.indices.seek(rank);
+    }
+
+    /// Splits the remaining combinations into two independent iterators: one
+    /// over the next `mid` of them, and one over the rest.
+    pub fn split_at(self, mid: u128) -> (Self, Self)
+    where T: Clone
+    {
+        let mut second = Self {
+            data: self.data.clone(),
+            indices: self.indices.clone(),
+        };
+        let mut first = self;
+
+        if !first.indices.done {
+            let at = first.indices.rank.saturating_add(mid);
+            second.indices.seek(at);
+            first.indices.end = Some(first.indices.end.map_or(at, |end| end.min(at)));
+        }
+
+        (first, second)
+    }
+
+    /// Splits the remaining combinations into at most `parts` iterators over
+    /// consecutive ranges of about the same size, for handing out to
+    /// workers.
+    pub fn split_into(self, parts: usize) -> Vec<Self>
+    where T: Clone
+    {
+        let Some(remaining) = self.remaining() else {
+            return vec![self];
+        };
+
+        let size = remaining.div_ceil(parts.max(1) as u128).max(1);
+        let mut ranges = vec![];
+        let mut rest = self;
+
+        while rest.remaining().is_some_and(|remaining| remaining > size) {
+            let (range, tail) = rest.split_at(size);
+            ranges.push(range);
+            rest = tail;
+        }
+
+        ranges.push(rest);
+        ranges
+    }
+}
+
 impl<T> Iterator for Combinations<T>
 where T: Clone
 {
     type Item = Vec<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.indices.next() {
-            None => None,
-            Some(indices) => Some(
-                indices
-                    .into_iter()
-                    .map(|i| self.data[i].clone())
-                    .collect()
-            ),
+        let Self { data, indices } = self;
+        indices
+            .next_indices()
+            .map(|c| c.iter().map(|&i| data[i].clone()).collect())
+    }
+
+    /// Jumps straight to the `n`th combination from here by unranking it,
+    /// rather than stepping through the ones before it.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if !self.indices.done {
+            self.indices.seek(self.indices.rank.saturating_add(n as u128));
         }
+
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.remaining())
     }
 }
 
@@ -108,11 +323,18 @@ where
     }
 }
 
+/// Non-decreasing indices of `k` of `n` items, in lexicographic order.
+/// Adding `i` to the `i`th index turns them into the strictly increasing
+/// indices of a combination of `k` of `n + k - 1` items, in the same order,
+/// which is how they're ranked. `rank` counts the combinations before the
+/// next one.
 struct CombinationsWithRepetitionIndices {
     c: Vec<usize>,
     n: usize,
     k: usize,
     first: bool,
+    done: bool,
+    rank: u128,
 }
 
 impl CombinationsWithRepetitionIndices {
@@ -125,14 +347,57 @@ impl CombinationsWithRepetitionIndices {
             n,
             k,
             first: true,
+            done: false,
+            rank: 0,
         }
     }
-}
 
-impl Iterator for CombinationsWithRepetitionIndices {
-    type Item = Vec<usize>;
+    /// Rank of the combination with the given indices, or `None` if they
+    /// aren't non-decreasing indices below `n`, or the rank overflows.
+    fn rank(&self, indices: &[usize]) -> Option<u128> {
+        if indices.len() != self.k || indices.windows(2).any(|w| w[0] > w[1]) || indices[self.k - 1] >= self.n {
+            return None;
+        }
+
+        let spread: Vec<_> = indices.iter().enumerate().map(|(i, &c)| c + i).collect();
+        rank_indices(self.n + self.k - 1, &spread)
+    }
+
+    /// Moves to the combination with rank `rank`, so it's the next one
+    /// returned. Past the last combination, there are none left.
+    fn seek(&mut self, rank: u128) {
+        if n_choose_k(self.n + self.k - 1, self.k).is_some_and(|total| rank >= total) {
+            self.done = true;
+            return;
+        }
+
+        self.rank = rank;
+        self.first = true;
+        self.done = false;
+        unrank_indices(self.n + self.k - 1, rank, &mut self.c);
+
+        for (i, c) in self.c.iter_mut().enumerate() {
+            *c -= i;
+        }
+    }
+
+    /// Number of combinations left to return, or `None` if it doesn't fit
+    /// in a `u128`.
+    fn remaining(&self) -> Option<u128> {
+        if self.done {
+            return Some(0);
+        }
+
+        let total = n_choose_k(self.n + self.k - 1, self.k)?;
+        Some(total.saturating_sub(self.rank))
+    }
+
+    /// Advances to the next combination, and borrows its indices.
+    fn next_indices(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
         if self.first {
             self.first = false;
         } else {
@@ -140,6 +405,7 @@ impl Iterator for CombinationsWithRepetitionIndices {
                 ref mut c,
                 ref n,
                 ref k,
+                ref mut done,
                 ..
             } = self;
             let mut i = k - 1;
@@ -155,13 +421,17 @@ impl Iterator for CombinationsWithRepetitionIndices {
                 }
 
                 i = match i.overflowing_sub(1) {
-                    (_, true) => return None,
+                    (_, true) => {
+                        *done = true;
+                        return None;
+                    },
                     (i, _) => i,
                 };
             }
         }
 
-        Some(self.c.clone())
+        self.rank += 1;
+        Some(&self.c)
     }
 }
 
@@ -170,21 +440,89 @@ pub struct CombinationsWithRepetition<T> {
     indices: CombinationsWithRepetitionIndices,
 }
 
+impl<T> CombinationsWithRepetition<T> {
+    /// Advances to the next combination, and borrows the indices of the
+    /// items in it, without cloning anything.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        self.indices.next_indices()
+    }
+
+    /// Calls `f` with each remaining combination, borrowing the items
+    /// rather than cloning them. Only allocates once, for the selection.
+    pub fn for_each_ref<F>(mut self, mut f: F)
+    where F: FnMut(&[&T])
+    {
+        let Self { data, indices } = &mut self;
+        let mut selection = Vec::with_capacity(indices.k);
+
+        while let Some(c) = indices.next_indices() {
+            selection.clear();
+            selection.extend(c.iter().map(|&i| &data[i]));
+            f(&selection);
+        }
+    }
+
+    /// Advances to the next combination, replacing the contents of `buffer`
+    /// with clones of its items. Returns false once there are none left.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool
+    where T: Clone
+    {
+        let Self { data, indices } = self;
+
+        match indices.next_indices() {
+            None => false,
+            Some(c) => {
+                buffer.clear();
+                buffer.extend(c.iter().map(|&i| data[i].clone()));
+                true
+            },
+        }
+    }
+
+    /// Position of the combination with the given item indices in the
+    /// lexicographic order this iterates in, counting from 0 for `k` copies
+    /// of the first item. `None` if the indices aren't non-decreasing and in
+    /// range, or the rank doesn't fit in a `u128`.
+    pub fn rank(&self, indices: &[usize]) -> Option<u128> {
+        self.indices.rank(indices)
+    }
+
+    /// Number of combinations left, or `None` if it doesn't fit in a `u128`.
+    pub fn remaining(&self) -> Option<u128> {
+        self.indices.remaining()
+    }
+
+    /// Moves to the combination with the given rank, so it's the next one
+    /// returned, whichever have been returned already.
+    pub fn seek(&mut self, rank: u128) {
+        self.indices.seek(rank);
+    }
+}
+
 impl<T> Iterator for CombinationsWithRepetition<T>
 where T: Clone
 {
     type Item = Vec<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.indices.next() {
-            None => None,
-            Some(indices) => Some(
-                indices
-                    .into_iter()
-                    .map(|i| self.data[i].clone())
-                    .collect()
-            ),
+        let Self { data, indices } = self;
+        indices
+            .next_indices()
+            .map(|c| c.iter().map(|&i| data[i].clone()).collect())
+    }
+
+    /// Jumps straight to the `n`th combination from here by unranking it,
+    /// rather than stepping through the ones before it.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if !self.indices.done {
+            self.indices.seek(self.indices.rank.saturating_add(n as u128));
         }
+
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.remaining())
     }
 }
 
@@ -217,13 +555,13 @@ mod tests {
     #[test]
     fn combination_indices() {
         let mut iter = super::CombinationIndices::new(4, 2);
-        assert_eq!(iter.next(), Some(vec![0, 1]));
-        assert_eq!(iter.next(), Some(vec![0, 2]));
-        assert_eq!(iter.next(), Some(vec![0, 3]));
-        assert_eq!(iter.next(), Some(vec![1, 2]));
-        assert_eq!(iter.next(), Some(vec![1, 3]));
-        assert_eq!(iter.next(), Some(vec![2, 3]));
-        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_indices(), Some(&[0, 1][..]));
+        assert_eq!(iter.next_indices(), Some(&[0, 2][..]));
+        assert_eq!(iter.next_indices(), Some(&[0, 3][..]));
+        assert_eq!(iter.next_indices(), Some(&[1, 2][..]));
+        assert_eq!(iter.next_indices(), Some(&[1, 3][..]));
+        assert_eq!(iter.next_indices(), Some(&[2, 3][..]));
+        assert_eq!(iter.next_indices(), None);
     }
 
     #[test]
@@ -254,6 +592,115 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn combinations_borrowed() {
+        use super::{CombinationsExt as _, CombinationsWithRepetitionExt as _};
+
+        let data = ["a", "b", "c", "d"];
+        let mut selections = vec![];
+        data.iter().combinations(3).for_each_ref(|c| selections.push(c.iter().map(|s| ***s).collect::<String>()));
+        assert_eq!(selections, ["abc", "abd", "acd", "bcd"]);
+
+        let mut iter = data.iter().combinations(2);
+        let mut buffer = vec![];
+        assert_eq!(iter.next_indices(), Some(&[0, 1][..]));
+        assert!(iter.next_into(&mut buffer));
+        assert_eq!(buffer, [&"a", &"c"]);
+        iter.next();
+        assert!(iter.next_into(&mut buffer));
+        assert_eq!(buffer, [&"b", &"c"]);
+        assert_eq!(iter.count(), 2);
+
+        let mut count = 0;
+        (0..3).combinations_with_repetition(2).for_each_ref(|c| {
+            assert!(c[0] <= c[1]);
+            count += 1;
+        });
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn combinations_random_access() {
+        use super::CombinationsExt as _;
+
+        let all: Vec<_> = (0..7).combinations(3).collect();
+        let iter = (0..7).combinations(3);
+        assert_eq!(iter.remaining(), Some(35));
+        for (rank, c) in all.iter().enumerate() {
+            assert_eq!(iter.rank(c), Some(rank as u128));
+            assert_eq!((0..7).combinations(3).nth(rank).as_ref(), Some(c));
+        }
+        assert_eq!(iter.rank(&[0, 0, 1]), None);
+        assert_eq!(iter.rank(&[2, 1, 0]), None);
+        assert_eq!(iter.rank(&[0, 1, 7]), None);
+        assert_eq!(iter.rank(&[0, 1]), None);
+
+        let mut iter = (0..7).combinations(3);
+        assert_eq!(iter.nth(3).as_ref(), Some(&all[3]));
+        assert_eq!(iter.nth(2).as_ref(), Some(&all[6]));
+        assert_eq!(iter.remaining(), Some(28));
+        assert_eq!(iter.size_hint(), (28, Some(28)));
+        assert_eq!(iter.nth(28), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.remaining(), Some(0));
+
+        iter.seek(33);
+        assert_eq!(iter.collect::<Vec<_>>(), &all[33..]);
+
+        let (first, second) = (0..7).combinations(3).split_at(10);
+        assert_eq!(first.collect::<Vec<_>>(), &all[..10]);
+        assert_eq!(second.collect::<Vec<_>>(), &all[10..]);
+
+        let mut iter = (0..7).combinations(3);
+        iter.nth(4);
+        let (first, second) = iter.split_at(100);
+        assert_eq!(first.collect::<Vec<_>>(), &all[5..]);
+        assert_eq!(second.count(), 0);
+
+        for parts in [1, 2, 3, 6, 35, 100] {
+            let ranges = (0..7).combinations(3).split_into(parts);
+            assert!(ranges.len() <= parts);
+            assert_eq!(ranges.into_iter().flatten().collect::<Vec<_>>(), all, "{} parts", parts);
+        }
+
+        let mut iter = (0..200).combinations(100);
+        assert_eq!(iter.remaining(), None);
+        let last: Vec<_> = (100..200).collect();
+        assert_eq!(iter.rank(&last), None);
+        iter.seek(1 << 100);
+        let c = iter.next_indices().unwrap().to_vec();
+        assert_eq!(iter.rank(&c), Some(1 << 100));
+    }
+
+    #[test]
+    fn combinations_with_repetition_random_access() {
+        use super::CombinationsWithRepetitionExt as _;
+
+        let all: Vec<_> = (0..5).combinations_with_repetition(3).collect();
+        let iter = (0..5).combinations_with_repetition(3);
+        assert_eq!(iter.remaining(), Some(35));
+        assert_eq!(all.len(), 35);
+        for (rank, c) in all.iter().enumerate() {
+            let indices: Vec<_> = c.iter().map(|&i| i as usize).collect();
+            assert_eq!(iter.rank(&indices), Some(rank as u128));
+            assert_eq!((0..5).combinations_with_repetition(3).nth(rank).as_ref(), Some(c));
+        }
+        assert_eq!(iter.rank(&[1, 0, 2]), None);
+        assert_eq!(iter.rank(&[0, 0, 5]), None);
+        assert_eq!(iter.rank(&[0, 0]), None);
+
+        let mut iter = (0..5).combinations_with_repetition(3);
+        assert_eq!(iter.nth(7).as_ref(), Some(&all[7]));
+        assert_eq!(iter.next().as_ref(), Some(&all[8]));
+        assert_eq!(iter.size_hint(), (26, Some(26)));
+        assert_eq!(iter.nth(26), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.remaining(), Some(0));
+
+        iter.seek(30);
+        assert_eq!(iter.collect::<Vec<_>>(), &all[30..]);
+    }
+
     #[test]
     fn combinations_with_repetition() {
         use super::CombinationsWithRepetitionExt as _;
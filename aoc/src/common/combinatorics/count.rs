@@ -0,0 +1,72 @@
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Number of ways to choose `k` of `n` items, ignoring their order, or
+/// `None` if it doesn't fit in a `u128`.
+pub fn n_choose_k(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+
+    for i in 0..k as u128 {
+        // result * (n - i) is always a multiple of i + 1, so dividing out
+        // the common factor first keeps the product exact without
+        // overflowing before the result itself would.
+        let divisor = i + 1;
+        let common = gcd(result, divisor);
+        result = (result / common).checked_mul((n as u128 - i) / (divisor / common))?;
+    }
+
+    Some(result)
+}
+
+/// Number of ordered arrangements of `k` of `n` items, or `None` if it
+/// doesn't fit in a `u128`.
+pub fn permutation_count(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+
+    (n - k + 1..=n).try_fold(1u128, |result, i| result.checked_mul(i as u128))
+}
+
+/// Iterator size hint for `remaining` items: exact if the count is known
+/// and fits in a `usize`, at least `usize::MAX` if it's known but doesn't,
+/// and anything if it isn't known.
+pub(super) fn size_hint(remaining: Option<u128>) -> (usize, Option<usize>) {
+    match remaining.map(usize::try_from) {
+        Some(Ok(remaining)) => (remaining, Some(remaining)),
+        Some(Err(_)) => (usize::MAX, None),
+        None => (0, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        assert_eq!(n_choose_k(5, 2), Some(10));
+        assert_eq!(n_choose_k(5, 0), Some(1));
+        assert_eq!(n_choose_k(0, 0), Some(1));
+        assert_eq!(n_choose_k(3, 5), Some(0));
+        assert_eq!(n_choose_k(130, 65), Some(95067625827960698145584333020095113100));
+        assert_eq!(n_choose_k(131, 65).map(|c| c > n_choose_k(130, 65).unwrap()), Some(true));
+        assert_eq!(n_choose_k(132, 66), None);
+
+        assert_eq!(permutation_count(5, 2), Some(20));
+        assert_eq!(permutation_count(5, 0), Some(1));
+        assert_eq!(permutation_count(3, 5), Some(0));
+        assert_eq!(permutation_count(34, 34), Some(295232799039604140847618609643520000000));
+        assert_eq!(permutation_count(35, 35), None);
+    }
+}
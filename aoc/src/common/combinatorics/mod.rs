@@ -1,4 +1,5 @@
 mod combination;
+mod count;
 mod permutation;
 
 pub use combination::{
@@ -6,6 +7,10 @@ pub use combination::{
     CombinationsWithRepetition, CombinationsWithRepetitionExt,
 };
 
+use count::size_hint;
+
+pub use count::{n_choose_k, permutation_count};
+
 pub use permutation::{
     LexicographicPermutations, LexicographicPermutationsExt,
     Permutations, PermutationsExt,
@@ -1,3 +1,7 @@
+use std::cmp::Ordering;
+
+use super::{n_choose_k, permutation_count, size_hint};
+
 struct PermutationIndices {
     indices: Vec<usize>,
     swaps: Vec<usize>,
@@ -12,13 +16,10 @@ impl PermutationIndices {
             i: 0,
         }
     }
-}
-
-/// Heap's algorithm
-impl Iterator for PermutationIndices {
-    type Item = Vec<usize>;
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Advances to the next permutation using Heap's algorithm, and borrows
+    /// its indices.
+    fn next_indices(&mut self) -> Option<&[usize]> {
         let Self {
             ref mut indices,
             ref mut swaps,
@@ -44,7 +45,7 @@ impl Iterator for PermutationIndices {
         }
 
         *i = 1;
-        Some(indices.clone())
+        Some(indices)
     }
 }
 
@@ -53,22 +54,56 @@ pub struct Permutations<T> {
     indices: PermutationIndices,
 }
 
+impl<T> Permutations<T> {
+    /// Advances to the next permutation, and borrows the indices of the
+    /// items in the order they appear in it, without cloning anything.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        self.indices.next_indices()
+    }
+
+    /// Calls `f` with each remaining permutation, borrowing the items rather
+    /// than cloning them. Only allocates once, for the permutation.
+    pub fn for_each_ref<F>(mut self, mut f: F)
+    where F: FnMut(&[&T])
+    {
+        let Self { data, indices } = &mut self;
+        let mut permutation = Vec::with_capacity(data.len());
+
+        while let Some(p) = indices.next_indices() {
+            permutation.clear();
+            permutation.extend(p.iter().map(|&i| &data[i]));
+            f(&permutation);
+        }
+    }
+
+    /// Advances to the next permutation, replacing the contents of `buffer`
+    /// with clones of its items. Returns false once there are none left.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool
+    where T: Clone
+    {
+        let Self { data, indices } = self;
+
+        match indices.next_indices() {
+            None => false,
+            Some(p) => {
+                buffer.clear();
+                buffer.extend(p.iter().map(|&i| data[i].clone()));
+                true
+            },
+        }
+    }
+}
+
 impl<T> Iterator for Permutations<T>
 where T: Clone
 {
     type Item = Vec<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(indices) = self.indices.next() {
-            Some(
-                indices
-                    .into_iter()
-                    .map(|i| self.data[i].clone())
-                    .collect()
-            )
-        } else {
-            None
-        }
+        let Self { data, indices } = self;
+        indices
+            .next_indices()
+            .map(|p| p.iter().map(|&i| data[i].clone()).collect())
     }
 }
 
@@ -77,7 +112,9 @@ where T: Clone
 {
     /// Creates iterator that generates all permutations of items in this collection.
     ///
-    /// Uses Heap's algorithm.
+    /// Uses Heap's algorithm, which can't jump ahead, so use
+    /// [lexicographic_permutations](LexicographicPermutationsExt::lexicographic_permutations)
+    /// for ranking or random access.
     fn permutations(&self) -> Permutations<T>;
 }
 
@@ -103,18 +140,105 @@ macro_rules! perms_ext_impl {
 
 perms_ext_impl!(Vec<T>, &[T], [T]);
 
+/// Indices of the items of `data` in ascending order, grouped into runs of
+/// equal items.
+fn sorted_groups<T: PartialOrd>(data: &[T]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..data.len()).collect();
+    order.sort_by(|&a, &b| data[a].partial_cmp(&data[b]).unwrap_or(Ordering::Equal));
+
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for i in order {
+        match groups.last_mut() {
+            Some(group) if data[group[0]] == data[i] => group.push(i),
+            _ => groups.push(vec![i]),
+        }
+    }
+
+    groups
+}
+
+/// Number of distinct arrangements of a multiset holding `counts[g]` copies
+/// of each item `g`, or `None` if it doesn't fit in a `u128`.
+fn arrangements(counts: &[usize]) -> Option<u128> {
+    let len = counts.iter().sum();
+
+    // With no repeated items, this is the base of the Lehmer code.
+    if counts.iter().all(|&count| count <= 1) {
+        return permutation_count(len, len);
+    }
+
+    let mut placed = 0;
+    counts.iter().try_fold(1u128, |result, &count| {
+        placed += count;
+        result.checked_mul(n_choose_k(placed, count)?)
+    })
+}
+
+/// Rank of `permutation` among the arrangements of the items in `data`,
+/// which is its Lehmer code when the items are distinct. `None` if it isn't
+/// an arrangement of the same items, or the rank doesn't fit in a `u128`.
+fn rank_arrangement<T: PartialOrd>(data: &[T], permutation: &[T]) -> Option<u128> {
+    if permutation.len() != data.len() {
+        return None;
+    }
+
+    let groups = sorted_groups(data);
+    let mut counts: Vec<usize> = groups.iter().map(Vec::len).collect();
+    let mut rank: u128 = 0;
+
+    for item in permutation {
+        let g = groups.iter().position(|group| data[group[0]] == *item)?;
+        if counts[g] == 0 {
+            return None;
+        }
+
+        // Counts the arrangements with a lower item here instead.
+        for lower in 0..g {
+            if counts[lower] == 0 {
+                continue;
+            }
+
+            counts[lower] -= 1;
+            rank = rank.checked_add(arrangements(&counts)?)?;
+            counts[lower] += 1;
+        }
+
+        counts[g] -= 1;
+    }
+
+    Some(rank)
+}
+
+/// Permutations of some items in lexicographic order, from the order they
+/// start in. `rank` is the rank of the next one, and `total` the number of
+/// arrangements, either of which is `None` if it doesn't fit in a `u128` or,
+/// for `rank`, the items can't all be compared.
 pub struct LexicographicPermutations<T> {
     data: Vec<T>,
     size: usize,
     first: bool,
+    rank: Option<u128>,
+    total: Option<u128>,
 }
 
-impl<T> Iterator for LexicographicPermutations<T>
-where T: Clone + PartialOrd
+impl<T> LexicographicPermutations<T>
+where T: PartialOrd
 {
-    type Item = Vec<T>;
+    fn new(data: Vec<T>) -> Self {
+        let counts: Vec<usize> = sorted_groups(&data).iter().map(Vec::len).collect();
 
-    fn next(&mut self) -> Option<Self::Item> {
+        Self {
+            rank: rank_arrangement(&data, &data),
+            total: arrangements(&counts),
+            size: data.len(),
+            data,
+            first: true,
+        }
+    }
+
+    /// Advances to the next permutation, which is rearranged in place, and
+    /// borrows it without cloning anything.
+    pub fn next_slice(&mut self) -> Option<&[T]> {
         if self.first {
             self.first = false;
         } else {
@@ -146,7 +270,121 @@ where T: Clone + PartialOrd
             let _ = &data[i..].reverse();
         }
 
-        Some(self.data.clone())
+        self.rank = self.rank.and_then(|rank| rank.checked_add(1));
+        Some(&self.data)
+    }
+
+    /// Position of `permutation` in the lexicographic order of every
+    /// arrangement of the items, counting from 0 for the items in ascending
+    /// order, and only counting arrangements of equal items once. For
+    /// distinct items this is the Lehmer code. `None` if it isn't an
+    /// arrangement of the same items, or the rank doesn't fit in a `u128`.
+    pub fn rank(&self, permutation: &[T]) -> Option<u128> {
+        rank_arrangement(&self.data, permutation)
+    }
+
+    /// Number of permutations left, or `None` if it doesn't fit in a `u128`,
+    /// or isn't known because the items can't all be compared.
+    pub fn remaining(&self) -> Option<u128> {
+        Some(self.total?.saturating_sub(self.rank?))
+    }
+
+    /// Moves to the permutation with the given rank, so it's the next one
+    /// returned, whichever have been returned already. Past the last
+    /// permutation, there are none left.
+    pub fn seek(&mut self, mut rank: u128) {
+        let groups = sorted_groups(&self.data);
+        let mut counts: Vec<usize> = groups.iter().map(Vec::len).collect();
+        let mut order = Vec::with_capacity(self.size);
+
+        if let Some(total) = self.total.filter(|&total| rank >= total) {
+            // The last permutation, already returned.
+            order.extend(groups.iter().rev().flatten());
+            self.first = false;
+            self.rank = Some(total);
+        } else {
+            self.rank = Some(rank);
+
+            for _ in 0..self.size {
+                for (g, group) in groups.iter().enumerate() {
+                    if counts[g] == 0 {
+                        continue;
+                    }
+
+                    counts[g] -= 1;
+
+                    // Skips past every arrangement with this item here.
+                    match arrangements(&counts) {
+                        Some(count) if rank >= count => {
+                            rank -= count;
+                            counts[g] += 1;
+                        },
+                        _ => {
+                            order.push(group[counts[g]]);
+                            break;
+                        },
+                    }
+                }
+            }
+
+            self.first = true;
+        }
+
+        let mut items: Vec<Option<T>> = std::mem::take(&mut self.data).into_iter().map(Some).collect();
+        self.data = order.into_iter().filter_map(|i| items[i].take()).collect();
+    }
+
+    /// Calls `f` with each remaining permutation, without cloning anything.
+    pub fn for_each_ref<F>(mut self, mut f: F)
+    where F: FnMut(&[T])
+    {
+        while let Some(permutation) = self.next_slice() {
+            f(permutation);
+        }
+    }
+
+    /// Advances to the next permutation, replacing the contents of `buffer`
+    /// with clones of its items. Returns false once there are none left.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool
+    where T: Clone
+    {
+        match self.next_slice() {
+            None => false,
+            Some(permutation) => {
+                buffer.clear();
+                buffer.extend_from_slice(permutation);
+                true
+            },
+        }
+    }
+}
+
+impl<T> Iterator for LexicographicPermutations<T>
+where T: Clone + PartialOrd
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+
+    /// Jumps straight to the `n`th permutation from here by unranking it,
+    /// rather than stepping through the ones before it.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self.rank {
+            Some(rank) => self.seek(rank.saturating_add(n as u128)),
+            None => {
+                for _ in 0..n {
+                    self.next_slice()?;
+                }
+            },
+        }
+
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint(self.remaining())
     }
 }
 
@@ -163,11 +401,7 @@ macro_rules! lex_perms_ext_impl {
         where T: Clone + PartialOrd
         {
             fn lexicographic_permutations(&self) -> LexicographicPermutations<T> {
-                LexicographicPermutations {
-                    data: self.to_vec(),
-                    size: self.len(),
-                    first: true,
-                }
+                LexicographicPermutations::new(self.to_vec())
             }
         }
     };
@@ -185,13 +419,13 @@ mod tests {
     #[test]
     fn permutation_indices() {
         let mut perms = super::PermutationIndices::new(3);
-        assert_eq!(perms.next(), Some(vec![0, 1, 2]));
-        assert_eq!(perms.next(), Some(vec![1, 0, 2]));
-        assert_eq!(perms.next(), Some(vec![2, 0, 1]));
-        assert_eq!(perms.next(), Some(vec![0, 2, 1]));
-        assert_eq!(perms.next(), Some(vec![1, 2, 0]));
-        assert_eq!(perms.next(), Some(vec![2, 1, 0]));
-        assert_eq!(perms.next(), None);
+        assert_eq!(perms.next_indices(), Some(&[0, 1, 2][..]));
+        assert_eq!(perms.next_indices(), Some(&[1, 0, 2][..]));
+        assert_eq!(perms.next_indices(), Some(&[2, 0, 1][..]));
+        assert_eq!(perms.next_indices(), Some(&[0, 2, 1][..]));
+        assert_eq!(perms.next_indices(), Some(&[1, 2, 0][..]));
+        assert_eq!(perms.next_indices(), Some(&[2, 1, 0][..]));
+        assert_eq!(perms.next_indices(), None);
     }
 
     #[test]
@@ -241,4 +475,83 @@ mod tests {
         assert_eq!(perms.next(), Some(vec![3, 2, 1]));
         assert_eq!(perms.next(), None);
     }
+
+    #[test]
+    fn lexicographic_permutations_random_access() {
+        use super::LexicographicPermutationsExt as _;
+
+        for data in [&[1, 2, 3, 4][..], &[1, 1, 2, 3], &[2, 2, 1, 1], &[5]] {
+            let all: Vec<_> = data.lexicographic_permutations().collect();
+            let mut sorted = data.to_vec();
+            sorted.sort();
+            let from_start: Vec<_> = sorted.lexicographic_permutations().collect();
+            let start = from_start.len() - all.len();
+
+            let perms = data.lexicographic_permutations();
+            assert_eq!(perms.remaining(), Some(all.len() as u128));
+            for (rank, p) in from_start.iter().enumerate() {
+                assert_eq!(perms.rank(p), Some(rank as u128), "{:?}", p);
+            }
+            for (n, p) in all.iter().enumerate() {
+                assert_eq!(data.lexicographic_permutations().nth(n).as_ref(), Some(p), "{:?}", data);
+            }
+
+            let mut perms = sorted.lexicographic_permutations();
+            perms.seek(start as u128);
+            assert_eq!(perms.collect::<Vec<_>>(), all);
+        }
+
+        let perms = [1, 1, 2].lexicographic_permutations();
+        assert_eq!(perms.rank(&[1, 2, 2]), None);
+        assert_eq!(perms.rank(&[1, 2]), None);
+
+        let mut perms = [1, 2, 3, 4].lexicographic_permutations();
+        assert_eq!(perms.nth(5), Some(vec![1, 4, 3, 2]));
+        assert_eq!(perms.next(), Some(vec![2, 1, 3, 4]));
+        assert_eq!(perms.size_hint(), (17, Some(17)));
+        assert_eq!(perms.nth(17), None);
+        assert_eq!(perms.next(), None);
+        assert_eq!(perms.remaining(), Some(0));
+
+        perms.seek(22);
+        assert_eq!(perms.collect::<Vec<_>>(), [vec![4, 3, 1, 2], vec![4, 3, 2, 1]]);
+
+        let nan = vec![2.0, f64::NAN, 1.0];
+        assert_eq!(nan.lexicographic_permutations().size_hint(), (0, None));
+        assert_eq!(nan.lexicographic_permutations().take(3).collect::<Vec<_>>().len(), 3);
+
+        let letters: Vec<_> = (0..40).collect();
+        let mut perms = letters.lexicographic_permutations();
+        assert_eq!(perms.remaining(), None);
+        perms.seek(1 << 100);
+        let p = perms.next_slice().unwrap().to_vec();
+        assert_eq!(perms.rank(&p), Some(1 << 100));
+    }
+
+    #[test]
+    fn permutations_borrowed() {
+        use super::{LexicographicPermutationsExt as _, PermutationsExt as _};
+
+        let data = ['a', 'b', 'c'];
+        let mut permutations = vec![];
+        data.permutations().for_each_ref(|p| permutations.push(p.iter().copied().collect::<String>()));
+        assert_eq!(permutations, ["abc", "bac", "cab", "acb", "bca", "cba"]);
+
+        let mut perms = data.permutations();
+        let mut buffer = vec![];
+        assert_eq!(perms.next_indices(), Some(&[0, 1, 2][..]));
+        assert!(perms.next_into(&mut buffer));
+        assert_eq!(buffer, ['b', 'a', 'c']);
+        assert_eq!(perms.count(), 4);
+
+        let mut permutations = vec![];
+        data.lexicographic_permutations().for_each_ref(|p| permutations.push(p.iter().collect::<String>()));
+        assert_eq!(permutations, ["abc", "acb", "bac", "bca", "cab", "cba"]);
+
+        let mut perms = data.lexicographic_permutations();
+        assert_eq!(perms.next_slice(), Some(&['a', 'b', 'c'][..]));
+        assert!(perms.next_into(&mut buffer));
+        assert_eq!(buffer, ['a', 'c', 'b']);
+        assert_eq!(perms.last(), Some(vec!['c', 'b', 'a']));
+    }
 }
@@ -0,0 +1,443 @@
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A point with `N` integer coordinates.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+/// A displacement between two [Point]s.
+pub type Vector<const N: usize> = Point<N>;
+
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Point4 = Point<4>;
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Self([0; N]);
+
+    /// Sum of the absolute differences of each coordinate.
+    pub fn manhattan(&self, other: &Self) -> i64 {
+        (0..N)
+            .map(|i| (self.0[i] - other.0[i]).abs())
+            .sum()
+    }
+
+    /// Largest absolute difference of any coordinate.
+    pub fn chebyshev(&self, other: &Self) -> i64 {
+        (0..N)
+            .map(|i| (self.0[i] - other.0[i]).abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The `2N` points that differ from this one by 1 in a single coordinate.
+    pub fn neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..N).flat_map(move |i| {
+            [-1, 1].map(|d| {
+                let mut neighbor = *self;
+                neighbor.0[i] += d;
+                neighbor
+            })
+        })
+    }
+
+    /// The `3^N - 1` points that differ from this one by at most 1 in every
+    /// coordinate, including diagonals.
+    pub fn neighbors_all(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..3usize.pow(N as u32))
+            .map(move |mut n| {
+                let mut neighbor = *self;
+                for c in neighbor.0.iter_mut() {
+                    *c += (n % 3) as i64 - 1;
+                    n /= 3;
+                }
+
+                neighbor
+            })
+            .filter(move |neighbor| neighbor != self)
+    }
+}
+
+impl Point<2> {
+    #[inline]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self([x, y])
+    }
+
+    #[inline]
+    pub const fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    #[inline]
+    pub const fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    /// Rotates by 90° counterclockwise around the origin.
+    #[inline]
+    pub const fn rotate_left(&self) -> Self {
+        Self::new(-self.y(), self.x())
+    }
+
+    /// Rotates by 90° clockwise around the origin.
+    #[inline]
+    pub const fn rotate_right(&self) -> Self {
+        Self::new(self.y(), -self.x())
+    }
+}
+
+impl Point<3> {
+    #[inline]
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self([x, y, z])
+    }
+
+    #[inline]
+    pub const fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    #[inline]
+    pub const fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    #[inline]
+    pub const fn z(&self) -> i64 {
+        self.0[2]
+    }
+}
+
+impl Point<4> {
+    #[inline]
+    pub const fn new(x: i64, y: i64, z: i64, w: i64) -> Self {
+        Self([x, y, z, w])
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Point<N> {
+    fn from(value: [i64; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+macro_rules! impl_point_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident) => {
+        impl<const N: usize> $op for Point<N> {
+            type Output = Self;
+
+            #[inline]
+            fn $fn(mut self, rhs: Self) -> Self {
+                self.$fn_assign(rhs);
+                self
+            }
+        }
+
+        impl<const N: usize> $op_assign for Point<N> {
+            #[inline]
+            fn $fn_assign(&mut self, rhs: Self) {
+                for (a, b) in self.0.iter_mut().zip(rhs.0) {
+                    a.$fn_assign(b);
+                }
+            }
+        }
+    };
+}
+
+impl_point_op!(Add, add, AddAssign, add_assign);
+impl_point_op!(Sub, sub, SubAssign, sub_assign);
+
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(mut self, rhs: i64) -> Self {
+        self *= rhs;
+        self
+    }
+}
+
+impl<const N: usize> MulAssign<i64> for Point<N> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: i64) {
+        self.0.iter_mut().for_each(|c| *c *= rhs);
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        self * -1
+    }
+}
+
+impl<const N: usize> std::fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    InvalidDirection(char),
+    InvalidTurn(char),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDirection(c) => write!(f, "invalid direction: {}", c)?,
+            Self::InvalidTurn(c) => write!(f, "invalid turn: {}", c)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A compass direction, with north being up.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Unit vector pointing in this direction, with `y` increasing to the north.
+    ///
+    /// For grids where `y` increases downwards, negate the `y` coordinate.
+    pub const fn vector(&self) -> Vector<2> {
+        match self {
+            Self::North => Point::<2>::new(0, 1),
+            Self::East => Point::<2>::new(1, 0),
+            Self::South => Point::<2>::new(0, -1),
+            Self::West => Point::<2>::new(-1, 0),
+        }
+    }
+
+    pub const fn turn_left(&self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::East => Self::North,
+            Self::South => Self::East,
+            Self::West => Self::South,
+        }
+    }
+
+    pub const fn turn_right(&self) -> Self {
+        match self {
+            Self::North => Self::East,
+            Self::East => Self::South,
+            Self::South => Self::West,
+            Self::West => Self::North,
+        }
+    }
+
+    pub const fn reverse(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+        }
+    }
+
+    pub const fn turn(&self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+}
+
+/// Parses an arrow (`^>v<`), a compass point (`NESW`), or an up, down, left
+/// or right instruction (`UDLR`).
+impl TryFrom<char> for Direction {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' | 'U' => Ok(Self::North),
+            '>' | 'E' | 'R' => Ok(Self::East),
+            'v' | 'S' | 'D' => Ok(Self::South),
+            '<' | 'W' | 'L' => Ok(Self::West),
+            c => Err(Error::InvalidDirection(c)),
+        }
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from(value as char)
+    }
+}
+
+/// A turn instruction.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// Parses `L` or `R`.
+impl TryFrom<char> for Turn {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Self::Left),
+            'R' => Ok(Self::Right),
+            c => Err(Error::InvalidTurn(c)),
+        }
+    }
+}
+
+impl TryFrom<u8> for Turn {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_from(value as char)
+    }
+}
+
+/// The smallest box containing a set of points, with inclusive bounds.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BoundingBox<const N: usize> {
+    pub min: Point<N>,
+    pub max: Point<N>,
+}
+
+impl<const N: usize> BoundingBox<N> {
+    /// A box containing only `point`.
+    pub fn new(point: Point<N>) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The bounding box of `points`, or `None` if there are no points.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where I: IntoIterator<Item = Point<N>>
+    {
+        let mut points = points.into_iter();
+        let mut bbox = Self::new(points.next()?);
+        points.for_each(|point| bbox.extend(point));
+
+        Some(bbox)
+    }
+
+    /// Grows the box to contain `point`.
+    pub fn extend(&mut self, point: Point<N>) {
+        for i in 0..N {
+            self.min[i] = self.min[i].min(point[i]);
+            self.max[i] = self.max[i].max(point[i]);
+        }
+    }
+
+    pub fn contains(&self, point: &Point<N>) -> bool {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    /// Number of points along each axis.
+    pub fn size(&self) -> [i64; N] {
+        std::array::from_fn(|i| self.max[i] - self.min[i] + 1)
+    }
+
+    /// Number of points in the box.
+    pub fn volume(&self) -> i64 {
+        self.size().iter().product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a + b, Point2::new(-2, 7));
+        assert_eq!(a - b, Point2::new(4, -3));
+        assert_eq!(-a * 2, Point2::new(-2, -4));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.rotate_left(), Point2::new(-2, 1));
+        assert_eq!(a.rotate_right(), Point2::new(2, -1));
+        assert_eq!(a.rotate_left().rotate_right(), a);
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "(1, 2, 3)");
+        assert_eq!(Point4::new(1, 1, 1, 1).manhattan(&Point4::ORIGIN), 4);
+
+        assert_eq!(Point2::ORIGIN.neighbors().count(), 4);
+        assert_eq!(Point2::ORIGIN.neighbors_all().count(), 8);
+        assert_eq!(Point3::ORIGIN.neighbors_all().count(), 26);
+        assert!(Point3::ORIGIN.neighbors().all(|p| p.manhattan(&Point3::ORIGIN) == 1));
+    }
+
+    #[test]
+    fn directions() {
+        let directions = "^>v<NESWUDLR"
+            .chars()
+            .map(|c| Direction::try_from(c).unwrap())
+            .collect::<Vec<_>>();
+        use Direction::*;
+        assert_eq!(directions, [North, East, South, West, North, East, South, West, North, South, West, East]);
+        assert_eq!(Direction::try_from('x'), Err(Error::InvalidDirection('x')));
+
+        assert_eq!(North.turn(Turn::try_from('L').unwrap()), West);
+        assert_eq!(North.turn(Turn::Right).turn(Turn::Right), North.reverse());
+        assert_eq!(Direction::ALL.map(|d| d.vector()).into_iter().fold(Point2::ORIGIN, Add::add), Point2::ORIGIN);
+        assert_eq!(East.vector().rotate_left(), North.vector());
+    }
+
+    #[test]
+    fn bounding_box() {
+        let bbox = BoundingBox::from_points([Point2::new(1, -2), Point2::new(-3, 4), Point2::new(0, 0)]).unwrap();
+        assert_eq!(bbox.min, Point2::new(-3, -2));
+        assert_eq!(bbox.max, Point2::new(1, 4));
+        assert_eq!(bbox.size(), [5, 7]);
+        assert_eq!(bbox.volume(), 35);
+        assert!(bbox.contains(&Point2::new(1, 4)));
+        assert!(!bbox.contains(&Point2::new(2, 0)));
+        assert_eq!(BoundingBox::<2>::from_points([]), None);
+    }
+}
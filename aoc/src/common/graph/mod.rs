@@ -0,0 +1,183 @@
+mod search;
+mod topological;
+pub mod tsp;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub use search::{astar, bfs, bfs_all, dijkstra, dijkstra_all, Paths};
+pub use topological::{topological_sort, Cycle};
+
+/// Directed graph with weighted edges between interned nodes.
+///
+/// Nodes are identified by a key (usually a name borrowed from the input)
+/// which is interned to a dense `usize` id on first use. The search
+/// functions in this module work on either, by passing
+/// [`Graph::successors`] as the successor function.
+#[derive(Clone, Debug)]
+pub struct Graph<K, W> {
+    keys: Vec<K>,
+    ids: HashMap<K, usize>,
+    edges: Vec<Vec<(usize, W)>>,
+}
+
+impl<K, W> Graph<K, W>
+where K: Clone + Eq + Hash
+{
+    pub fn new() -> Self {
+        Self {
+            keys: vec![],
+            ids: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// Returns the id of `key`, adding it as a new node if it is not yet in
+    /// the graph.
+    pub fn intern(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+
+        let id = self.keys.len();
+        self.keys.push(key.clone());
+        self.ids.insert(key, id);
+        self.edges.push(vec![]);
+
+        id
+    }
+
+    pub fn id(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: usize) -> &K {
+        &self.keys[id]
+    }
+
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Adds an edge from `from` to `to`, replacing the weight of any existing
+    /// edge between them. Returns the ids of both nodes.
+    pub fn add_edge(&mut self, from: K, to: K, weight: W) -> (usize, usize) {
+        let (from, to) = (self.intern(from), self.intern(to));
+        let edges = &mut self.edges[from];
+
+        match edges.iter_mut().find(|(id, _)| *id == to) {
+            Some(edge) => edge.1 = weight,
+            None => edges.push((to, weight)),
+        }
+
+        (from, to)
+    }
+
+    /// Adds an edge in both directions between `a` and `b`.
+    pub fn add_undirected_edge(&mut self, a: K, b: K, weight: W) -> (usize, usize)
+    where W: Clone
+    {
+        let (a, b) = self.add_edge(a, b, weight.clone());
+        self.add_edge(self.keys[b].clone(), self.keys[a].clone(), weight);
+
+        (a, b)
+    }
+
+    /// Outgoing edges of node `id`, as `(to, weight)` pairs.
+    pub fn edges(&self, id: usize) -> &[(usize, W)] {
+        &self.edges[id]
+    }
+
+    pub fn weight(&self, from: usize, to: usize) -> Option<&W> {
+        self.edges[from]
+            .iter()
+            .find(|(id, _)| *id == to)
+            .map(|(_, weight)| weight)
+    }
+
+    /// Edge weights indexed by node ids, with `missing` wherever there is no
+    /// edge. Used as the cost matrix for [tsp::solve].
+    pub fn cost_matrix(&self, missing: W) -> Vec<Vec<W>>
+    where W: Clone
+    {
+        self.edges
+            .iter()
+            .map(|edges| {
+                let mut row = vec![missing.clone(); self.len()];
+                edges.iter().for_each(|(to, weight)| row[*to] = weight.clone());
+                row
+            })
+            .collect()
+    }
+
+    /// Outgoing edges of node `id`, in the shape expected by [dijkstra] and
+    /// [astar].
+    pub fn successors(&self, id: usize) -> impl Iterator<Item = (usize, W)> + '_
+    where W: Clone
+    {
+        self.edges[id].iter().cloned()
+    }
+
+    /// Orders the node ids so that every edge points forwards.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Cycle<usize>> {
+        let ids: Vec<_> = (0..self.len()).collect();
+
+        topological_sort(&ids, |&id| self.edges[id].iter().map(|&(to, _)| to))
+    }
+}
+
+impl<K, W> Default for Graph<K, W>
+where K: Clone + Eq + Hash
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph() {
+        let mut graph = Graph::new();
+        assert_eq!(graph.add_undirected_edge("London", "Dublin", 464), (0, 1));
+        graph.add_undirected_edge("London", "Belfast", 518);
+        graph.add_undirected_edge("Dublin", "Belfast", 141);
+        graph.add_edge("Dublin", "Belfast", 140);
+
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.id(&"Belfast"), Some(2));
+        assert_eq!(graph.id(&"Paris"), None);
+        assert_eq!(graph.key(1), &"Dublin");
+        assert_eq!(graph.weight(1, 2), Some(&140));
+        assert_eq!(graph.weight(2, 1), Some(&141));
+        assert_eq!(graph.edges(0), &[(1, 464), (2, 518)]);
+        assert_eq!(graph.cost_matrix(0), [[0, 464, 518], [464, 0, 140], [518, 141, 0]]);
+
+        let (path, cost) = dijkstra(0, |&id| graph.successors(id), |&id| id == 2).unwrap();
+        assert_eq!(path, [0, 2]);
+        assert_eq!(cost, 518);
+
+        let mut graph: Graph<&str, ()> = Graph::new();
+        graph.add_edge("x", "d", ());
+        graph.add_edge("y", "d", ());
+        graph.add_edge("d", "h", ());
+        graph.add_edge("x", "h", ());
+        let order = graph.topological_sort().unwrap();
+        let keys: Vec<_> = order.iter().map(|&id| *graph.key(id)).collect();
+        assert_eq!(keys, ["x", "y", "d", "h"]);
+
+        graph.add_edge("h", "x", ());
+        assert!(graph.topological_sort().is_err());
+    }
+}
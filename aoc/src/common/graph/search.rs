@@ -0,0 +1,258 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Shortest paths from a single start node, as found by [bfs_all] or
+/// [dijkstra_all].
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    nodes: HashMap<N, (Option<N>, C)>,
+}
+
+impl<N, C> Paths<N, C>
+where N: Clone + Eq + Hash,
+      C: Copy,
+{
+    /// Cost of the cheapest path to `node`, or `None` if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(_, cost)| cost)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    /// Reconstructs the cheapest path from the start node to `node`,
+    /// including both ends.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut curr = self.nodes.get(node)?;
+
+        while let (Some(parent), _) = curr {
+            path.push(parent.clone());
+            curr = &self.nodes[parent];
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Number of reached nodes, including the start node.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Reached nodes and the cost to reach them, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, &(_, cost))| (node, cost))
+    }
+}
+
+fn breadth_first<N, FN, IN, FS>(start: N, mut successors: FN, mut success: FS) -> (Paths<N, usize>, Option<N>)
+where N: Clone + Eq + Hash,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = N>,
+      FS: FnMut(&N) -> bool,
+{
+    let mut nodes = HashMap::new();
+    let mut queue = VecDeque::new();
+    nodes.insert(start.clone(), (None, 0));
+    queue.push_back((start, 0));
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if success(&node) {
+            return (Paths { nodes }, Some(node));
+        }
+
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = nodes.entry(next.clone()) {
+                entry.insert((Some(node.clone()), steps + 1));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    (Paths { nodes }, None)
+}
+
+/// Finds a path with the fewest steps from `start` to any node for which
+/// `success` returns true.
+pub fn bfs<N, FN, IN, FS>(start: N, successors: FN, success: FS) -> Option<Vec<N>>
+where N: Clone + Eq + Hash,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = N>,
+      FS: FnMut(&N) -> bool,
+{
+    let (paths, goal) = breadth_first(start, successors, success);
+
+    goal.and_then(|goal| paths.path_to(&goal))
+}
+
+/// Finds the number of steps to every node reachable from `start`.
+pub fn bfs_all<N, FN, IN>(start: N, successors: FN) -> Paths<N, usize>
+where N: Clone + Eq + Hash,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = N>,
+{
+    breadth_first(start, successors, |_| false).0
+}
+
+fn best_first<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> (Paths<N, C>, Option<N>)
+where N: Clone + Eq + Hash,
+      C: Copy + Default + Ord + Add<Output = C>,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = (N, C)>,
+      FH: FnMut(&N) -> C,
+      FS: FnMut(&N) -> bool,
+{
+    let mut nodes = HashMap::new();
+    // Nodes aren't required to be `Ord`, so the heap holds indices into
+    // `queued` instead, ordered by estimated total cost.
+    let mut queued = vec![];
+    let mut heap = BinaryHeap::new();
+
+    nodes.insert(start.clone(), (None, C::default()));
+    heap.push(Reverse((heuristic(&start), 0)));
+    queued.push((start, C::default()));
+
+    while let Some(Reverse((_, i))) = heap.pop() {
+        let (node, cost) = queued[i].clone();
+        if nodes[&node].1 < cost {
+            // Found a cheaper path to this node after it was queued.
+            continue;
+        }
+
+        if success(&node) {
+            return (Paths { nodes }, Some(node));
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+
+            match nodes.entry(next.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert((Some(node.clone()), next_cost));
+                },
+                Entry::Occupied(mut entry) if next_cost < entry.get().1 => {
+                    entry.insert((Some(node.clone()), next_cost));
+                },
+                Entry::Occupied(_) => continue,
+            }
+
+            heap.push(Reverse((next_cost + heuristic(&next), queued.len())));
+            queued.push((next, next_cost));
+        }
+    }
+
+    (Paths { nodes }, None)
+}
+
+/// Finds the cheapest path from `start` to any node for which `success`
+/// returns true, returning the path and its cost.
+///
+/// `successors` returns each neighbouring node with the cost of moving to
+/// it, which must not be negative.
+pub fn dijkstra<N, C, FN, IN, FS>(start: N, successors: FN, success: FS) -> Option<(Vec<N>, C)>
+where N: Clone + Eq + Hash,
+      C: Copy + Default + Ord + Add<Output = C>,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = (N, C)>,
+      FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// Finds the cost of the cheapest path to every node reachable from `start`.
+pub fn dijkstra_all<N, C, FN, IN>(start: N, successors: FN) -> Paths<N, C>
+where N: Clone + Eq + Hash,
+      C: Copy + Default + Ord + Add<Output = C>,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, successors, |_| C::default(), |_| false).0
+}
+
+/// Like [dijkstra], but guided by `heuristic`, which estimates the remaining
+/// cost from a node to the goal. The path found is only guaranteed to be the
+/// cheapest if the estimate never exceeds the real cost.
+pub fn astar<N, C, FN, IN, FH, FS>(start: N, successors: FN, heuristic: FH, success: FS) -> Option<(Vec<N>, C)>
+where N: Clone + Eq + Hash,
+      C: Copy + Default + Ord + Add<Output = C>,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = (N, C)>,
+      FH: FnMut(&N) -> C,
+      FS: FnMut(&N) -> bool,
+{
+    let (paths, goal) = best_first(start, successors, heuristic, success);
+    let goal = goal?;
+
+    Some((paths.path_to(&goal)?, paths.cost(&goal)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::geometry::Point2;
+
+    fn open(p: &Point2) -> bool {
+        // A wall along x = 2 with a gap at y = 3.
+        (0..5).contains(&p.x()) && (0..5).contains(&p.y()) && (p.x() != 2 || p.y() == 3)
+    }
+
+    #[test]
+    fn breadth_first_search() {
+        let successors = |p: &Point2| p.neighbors().filter(open).collect::<Vec<_>>();
+        let goal = Point2::new(4, 0);
+
+        let path = bfs(Point2::ORIGIN, successors, |p| *p == goal).unwrap();
+        assert_eq!(path.len() - 1, 10);
+        assert_eq!(path.first(), Some(&Point2::ORIGIN));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+
+        let paths = bfs_all(Point2::ORIGIN, successors);
+        assert_eq!(paths.len(), 21);
+        assert_eq!(paths.cost(&goal), Some(10));
+        assert_eq!(paths.path_to(&goal).map(|p| p.len()), Some(11));
+        assert_eq!(paths.cost(&Point2::new(2, 0)), None);
+
+        assert_eq!(bfs(Point2::ORIGIN, successors, |p| p.x() > 10), None);
+    }
+
+    #[test]
+    fn weighted_search() {
+        // Moving up costs 3, every other direction costs 1.
+        let successors = |p: &Point2| {
+            let p = *p;
+            p.neighbors()
+                .filter(open)
+                .map(move |n| (n, if n.y() > p.y() { 3 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+        let goal = Point2::new(4, 4);
+
+        let (path, cost) = dijkstra(Point2::ORIGIN, successors, |p| *p == goal).unwrap();
+        assert_eq!(cost, 16);
+        assert_eq!(path.len(), 9);
+
+        let (path, cost) = astar(Point2::ORIGIN, successors, |p| p.manhattan(&goal), |p| *p == goal).unwrap();
+        assert_eq!(cost, 16);
+        assert_eq!(path.len(), 9);
+
+        let paths = dijkstra_all(Point2::ORIGIN, successors);
+        assert_eq!(paths.cost(&goal), Some(16));
+        assert_eq!(paths.cost(&Point2::new(0, 4)), Some(12));
+        assert_eq!(paths.cost(&Point2::new(4, 0)), Some(16));
+    }
+}
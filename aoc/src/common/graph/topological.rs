@@ -0,0 +1,161 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Nodes forming a cycle, which prevents a topological ordering. Each node
+/// has an edge to the next, and the last node has an edge to the first.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle<N> {
+    pub nodes: Vec<N>,
+}
+
+impl<N> Cycle<N> {
+    pub fn map<F, M>(self, f: F) -> Cycle<M>
+    where F: FnMut(N) -> M
+    {
+        Cycle {
+            nodes: self.nodes.into_iter().map(f).collect(),
+        }
+    }
+}
+
+impl<N> std::fmt::Display for Cycle<N>
+where N: Debug
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cycle detected: ")?;
+        for node in self.nodes.iter() {
+            write!(f, "{:?} -> ", node)?;
+        }
+        if let Some(first) = self.nodes.first() {
+            write!(f, "{:?}", first)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<N> std::error::Error for Cycle<N>
+where N: Debug
+{}
+
+/// Orders `nodes` so that every node comes before its successors, using
+/// Kahn's algorithm. Successors that are not in `nodes` are included as well.
+///
+/// Nodes that don't depend on each other keep their relative order from
+/// `nodes`. If the nodes can't be ordered, one of the cycles is returned.
+pub fn topological_sort<N, FN, IN>(nodes: &[N], mut successors: FN) -> Result<Vec<N>, Cycle<N>>
+where N: Clone + Eq + Hash,
+      FN: FnMut(&N) -> IN,
+      IN: IntoIterator<Item = N>,
+{
+    let mut keys: Vec<N> = vec![];
+    let mut ids: HashMap<N, usize> = HashMap::new();
+    let mut intern = |node: &N, keys: &mut Vec<N>| {
+        *ids.entry(node.clone()).or_insert_with(|| {
+            keys.push(node.clone());
+            keys.len() - 1
+        })
+    };
+
+    nodes.iter().for_each(|node| { intern(node, &mut keys); });
+
+    let mut edges: Vec<Vec<usize>> = vec![];
+    let mut i = 0;
+    while i < keys.len() {
+        let next: Vec<_> = successors(&keys[i])
+            .into_iter()
+            .map(|node| intern(&node, &mut keys))
+            .collect();
+        edges.push(next);
+        i += 1;
+    }
+
+    let mut in_degree = vec![0; keys.len()];
+    edges.iter().flatten().for_each(|&to| in_degree[to] += 1);
+
+    let mut queue: VecDeque<_> = (0..keys.len())
+        .filter(|&id| in_degree[id] == 0)
+        .collect();
+    let mut order = Vec::with_capacity(keys.len());
+
+    while let Some(id) = queue.pop_front() {
+        order.push(id);
+
+        for &to in edges[id].iter() {
+            in_degree[to] -= 1;
+            if in_degree[to] == 0 {
+                queue.push_back(to);
+            }
+        }
+    }
+
+    if order.len() < keys.len() {
+        return Err(find_cycle(&edges, &in_degree).map(|id| keys[id].clone()));
+    }
+
+    Ok(order.into_iter().map(|id| keys[id].clone()).collect())
+}
+
+/// Every node left with incoming edges after Kahn's algorithm has at least one
+/// predecessor that was also left, so walking backwards through those must
+/// eventually revisit a node.
+fn find_cycle(edges: &[Vec<usize>], in_degree: &[usize]) -> Cycle<usize> {
+    let mut predecessor = vec![None; edges.len()];
+    for (from, next) in edges.iter().enumerate() {
+        if in_degree[from] > 0 {
+            next.iter().for_each(|&to| predecessor[to] = Some(from));
+        }
+    }
+
+    let mut position = vec![None; edges.len()];
+    let mut walk = vec![];
+    let mut curr = in_degree.iter().position(|&d| d > 0).unwrap();
+
+    while position[curr].is_none() {
+        position[curr] = Some(walk.len());
+        walk.push(curr);
+        curr = predecessor[curr].unwrap();
+    }
+
+    let mut nodes = walk.split_off(position[curr].unwrap());
+    nodes.reverse();
+
+    Cycle { nodes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topological() {
+        let edges: HashMap<_, Vec<_>> = HashMap::from([
+            ("shirt", vec!["tie", "belt"]),
+            ("tie", vec!["jacket"]),
+            ("trousers", vec!["shoes", "belt"]),
+            ("belt", vec!["jacket"]),
+            ("socks", vec!["shoes"]),
+        ]);
+        let successors = |node: &&str| edges.get(node).cloned().unwrap_or_default();
+
+        let order = topological_sort(&["socks", "shirt", "trousers"], successors).unwrap();
+        assert_eq!(order, ["socks", "shirt", "trousers", "tie", "shoes", "belt", "jacket"]);
+
+        let edges: HashMap<_, Vec<_>> = HashMap::from([
+            ("a", vec!["b"]),
+            ("b", vec!["c", "e"]),
+            ("c", vec!["d"]),
+            ("d", vec!["b"]),
+        ]);
+        let successors = |node: &&str| edges.get(node).cloned().unwrap_or_default();
+
+        let cycle = topological_sort(&["a"], successors).unwrap_err();
+        assert_eq!(cycle.nodes.len(), 3);
+        for (i, node) in cycle.nodes.iter().enumerate() {
+            let next = cycle.nodes[(i + 1) % cycle.nodes.len()];
+            assert!(edges[node].contains(&next));
+        }
+        assert_eq!(cycle.map(|_| 0).to_string(), "cycle detected: 0 -> 0 -> 0 -> 0");
+    }
+}
@@ -0,0 +1,161 @@
+//! Exact travelling salesman solver using the Held–Karp algorithm, which runs
+//! in `O(2^n * n^2)` time and `O(2^n * n)` memory, so is limited to
+//! [MAX_NODES] nodes.
+
+use std::ops::Add;
+
+/// Most nodes [solve] accepts. Its tables have `2^n * n` entries, which at 20
+/// nodes with 8 byte costs already take around 500 MB, and each extra node
+/// doubles that.
+pub const MAX_NODES: usize = 20;
+
+/// Whether the route has to return to where it started.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Route {
+    /// Visits every node once, starting and ending anywhere.
+    Path,
+    /// Visits every node once and returns to the first.
+    Cycle,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Objective {
+    Minimize,
+    Maximize,
+}
+
+impl Objective {
+    fn is_better<C: Ord>(&self, a: C, b: C) -> bool {
+        match self {
+            Self::Minimize => a < b,
+            Self::Maximize => a > b,
+        }
+    }
+}
+
+/// An optimal route, with nodes in the order they are visited.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tour<C> {
+    pub cost: C,
+    pub order: Vec<usize>,
+}
+
+/// Finds the cheapest (or most expensive) route through every node, where
+/// `costs[a][b]` is the cost of going from `a` to `b`. For a [Route::Cycle],
+/// the order starts at node 0 and the cost includes the edge back to it.
+///
+/// Returns `None` if there are no nodes.
+///
+/// # Panics
+///
+/// Panics if `costs` is not square, or has more than [MAX_NODES] nodes.
+pub fn solve<C>(costs: &[Vec<C>], route: Route, objective: Objective) -> Option<Tour<C>>
+where C: Copy + Default + Ord + Add<Output = C>
+{
+    let n = costs.len();
+    assert!(costs.iter().all(|row| row.len() == n), "cost matrix must be square");
+    assert!(n <= MAX_NODES, "{} nodes is too many for Held-Karp, which is limited to {}", n, MAX_NODES);
+
+    if n == 0 {
+        return None;
+    }
+
+    // best[set * n + last] is the best cost of visiting every node in `set`,
+    // ending at `last`, and prev[set * n + last] the node visited before it.
+    let full = (1 << n) - 1;
+    let mut best: Vec<Option<C>> = vec![None; (full + 1) * n];
+    let mut prev = vec![usize::MAX; (full + 1) * n];
+
+    match route {
+        Route::Path => (0..n).for_each(|i| best[(1 << i) * n + i] = Some(C::default())),
+        Route::Cycle => best[n] = Some(C::default()),
+    }
+
+    for set in 1..=full {
+        for last in (0..n).filter(|&last| set & (1 << last) != 0) {
+            let Some(cost) = best[set * n + last] else {
+                continue;
+            };
+
+            for next in (0..n).filter(|&next| set & (1 << next) == 0) {
+                let i = (set | (1 << next)) * n + next;
+                let next_cost = cost + costs[last][next];
+
+                if best[i].is_none_or(|b| objective.is_better(next_cost, b)) {
+                    best[i] = Some(next_cost);
+                    prev[i] = last;
+                }
+            }
+        }
+    }
+
+    let closing = |last: usize| match route {
+        Route::Path => C::default(),
+        Route::Cycle => costs[last][0],
+    };
+
+    let (mut last, cost) = (0..n)
+        .filter_map(|last| best[full * n + last].map(|cost| (last, cost + closing(last))))
+        .reduce(|a, b| if objective.is_better(b.1, a.1) { b } else { a })?;
+
+    let mut order = Vec::with_capacity(n);
+    let mut set = full;
+    while last != usize::MAX {
+        order.push(last);
+        let i = set * n + last;
+        set &= !(1 << last);
+        last = prev[i];
+    }
+    order.reverse();
+
+    Some(Tour { cost, order })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PermutationsExt;
+
+    fn route_cost(costs: &[Vec<i64>], order: &[usize], route: Route) -> i64 {
+        let mut cost = order.windows(2).map(|w| costs[w[0]][w[1]]).sum();
+        if route == Route::Cycle {
+            cost += costs[order[order.len() - 1]][order[0]];
+        }
+
+        cost
+    }
+
+    #[test]
+    fn held_karp() {
+        // Asymmetric, so the direction of travel matters.
+        let costs: Vec<Vec<i64>> = (0..7)
+            .map(|a| (0..7).map(|b| (a * 7 + b * 3) % 11 + if a < b { 5 } else { 0 }).collect())
+            .collect();
+        let nodes: Vec<_> = (0..7).collect();
+
+        for route in [Route::Path, Route::Cycle] {
+            let costs_of = || nodes.permutations().map(|order| route_cost(&costs, &order, route));
+
+            let tour = solve(&costs, route, Objective::Minimize).unwrap();
+            assert_eq!(tour.cost, costs_of().min().unwrap());
+            assert_eq!(tour.cost, route_cost(&costs, &tour.order, route));
+
+            let tour = solve(&costs, route, Objective::Maximize).unwrap();
+            assert_eq!(tour.cost, costs_of().max().unwrap());
+            assert_eq!(tour.cost, route_cost(&costs, &tour.order, route));
+
+            let mut order = tour.order.clone();
+            order.sort();
+            assert_eq!(order, nodes);
+        }
+
+        assert_eq!(solve(&[vec![0]], Route::Path, Objective::Minimize), Some(Tour { cost: 0, order: vec![0] }));
+        assert_eq!(solve::<i64>(&[], Route::Cycle, Objective::Minimize), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "21 nodes is too many for Held-Karp, which is limited to 20")]
+    fn too_many_nodes() {
+        solve(&vec![vec![0i64; MAX_NODES + 1]; MAX_NODES + 1], Route::Path, Objective::Minimize);
+    }
+}
@@ -0,0 +1,609 @@
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
+
+use crate::input::FromInput;
+
+const NEIGHBORS_4: [(i64, i64); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+const NEIGHBORS_8: [(i64, i64); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1, 0), (1, 0),
+    (-1, 1), (0, 1), (1, 1),
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A row with a different width to the first row.
+    RaggedRow { row: usize, expected: usize, found: usize },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RaggedRow { row, expected, found } => write!(f, "row {} has width {}, expected {}", row, found, expected)?,
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Cells are indexed by `(x, y)`, with `(0, 0)` in the top left corner, `x`
+/// increasing to the right, and `y` increasing downwards.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where T: Clone
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid with each cell set to the result of calling `f` with its position.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where F: FnMut(usize, usize) -> T
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid from rows of bytes, converting each byte with `f`.
+    ///
+    /// Every row must have the same width.
+    pub fn from_rows<R, F>(rows: &[R], mut f: F) -> Result<Self, Error>
+    where
+        R: AsRef<[u8]>,
+        F: FnMut(u8) -> T,
+    {
+        let width = rows.first().map_or(0, |row| row.as_ref().len());
+        let mut cells = Vec::with_capacity(width * rows.len());
+
+        for (y, row) in rows.iter().enumerate() {
+            let row = row.as_ref();
+            if row.len() != width {
+                return Err(Error::RaggedRow { row: y, expected: width, found: row.len() });
+            }
+
+            cells.extend(row.iter().map(|&b| f(b)));
+        }
+
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells,
+        })
+    }
+
+    /// Creates a grid from rows of cells.
+    ///
+    /// Every row must have the same width.
+    pub fn from_cells(rows: Vec<Vec<T>>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::RaggedRow { row: y, expected: width, found: row.len() });
+            }
+
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Creates a grid from lines of text, converting each byte with `f`.
+    pub fn parse<F>(input: &str, f: F) -> Result<Self, Error>
+    where F: FnMut(u8) -> T
+    {
+        let rows = input
+            .lines()
+            .collect::<Vec<_>>();
+
+        Self::from_rows(&rows, f)
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Converts a signed position to a position in the grid, if it is within bounds.
+    #[inline]
+    pub fn position(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        let x = usize::try_from(x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|&y| y < self.height)?;
+
+        Some((x, y))
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Gets the cell at a signed position, or `None` if it is outside the grid.
+    #[inline]
+    pub fn get_signed(&self, x: i64, y: i64) -> Option<&T> {
+        self.position(x, y).and_then(|(x, y)| self.get(x, y))
+    }
+
+    /// Gets the cell at a signed position, wrapping around the edges of the
+    /// grid in both directions.
+    ///
+    /// Panics if the grid is empty.
+    #[inline]
+    pub fn get_wrapping(&self, x: i64, y: i64) -> &T {
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.rem_euclid(self.height as i64) as usize;
+
+        &self[(x, y)]
+    }
+
+    /// Positions of the orthogonal neighbours of `(x, y)` that are within the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &NEIGHBORS_4)
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours of `(x, y)` that
+    /// are within the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, &NEIGHBORS_8)
+    }
+
+    fn neighbors<'a>(&'a self, x: usize, y: usize, offsets: &'static [(i64, i64)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| self.position(x as i64 + dx, y as i64 + dy))
+    }
+
+    #[inline]
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Not `chunks_exact`, which would yield no rows at all for a width
+        // of 0, rather than `height` empty ones.
+        (0..self.height).map(move |y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of range for grid of width {}", x, self.width);
+
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates over every cell, row by row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates over the position and value of every cell, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Finds the position of the first cell, row by row, matching `predicate`.
+    pub fn find<P>(&self, mut predicate: P) -> Option<(usize, usize)>
+    where P: FnMut(&T) -> bool
+    {
+        self.enumerate()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Counts the cells matching `predicate`.
+    pub fn count<P>(&self, mut predicate: P) -> usize
+    where P: FnMut(&T) -> bool
+    {
+        self.cells
+            .iter()
+            .filter(|cell| predicate(cell))
+            .count()
+    }
+
+    fn bounds<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+
+        (start, end)
+    }
+
+    fn resolve<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+        let (start, end) = Self::bounds(range, len);
+        assert!(start <= end && end <= len, "range {}..{} out of bounds for length {}", start, end, len);
+
+        start..end
+    }
+
+    /// Like [resolve](Self::resolve), but cuts the range down to `0..len`,
+    /// leaving it empty if it doesn't overlap, rather than panicking.
+    fn clamp<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+        let (start, end) = Self::bounds(range, len);
+        let start = start.min(len);
+
+        start..end.clamp(start, len)
+    }
+
+    fn rows_mut_in(&mut self, xs: Range<usize>, ys: Range<usize>) -> impl Iterator<Item = &mut T> {
+        let width = self.width;
+        let mut rest = &mut self.cells[ys.start * width..ys.end * width];
+
+        ys.flat_map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(width);
+            rest = tail;
+            row[xs.clone()].iter_mut()
+        })
+    }
+
+    fn rows_in(&self, xs: Range<usize>, ys: Range<usize>) -> impl Iterator<Item = &T> {
+        self.rows()
+            .skip(ys.start)
+            .take(ys.len())
+            .flat_map(move |row| row[xs.clone()].iter())
+    }
+
+    /// Iterates mutably over the cells in a rectangular region, row by row,
+    /// such as `grid.region_mut(x1..=x2, y1..=y2)`.
+    ///
+    /// Panics if the region isn't within the grid.
+    pub fn region_mut<X, Y>(&mut self, xs: X, ys: Y) -> impl Iterator<Item = &mut T>
+    where
+        X: RangeBounds<usize>,
+        Y: RangeBounds<usize>,
+    {
+        let xs = Self::resolve(xs, self.width);
+        let ys = Self::resolve(ys, self.height);
+
+        self.rows_mut_in(xs, ys)
+    }
+
+    /// Iterates over the cells in a rectangular region, row by row.
+    ///
+    /// Panics if the region isn't within the grid.
+    pub fn region<X, Y>(&self, xs: X, ys: Y) -> impl Iterator<Item = &T>
+    where
+        X: RangeBounds<usize>,
+        Y: RangeBounds<usize>,
+    {
+        let xs = Self::resolve(xs, self.width);
+        let ys = Self::resolve(ys, self.height);
+
+        self.rows_in(xs, ys)
+    }
+
+    /// Like [region_mut](Self::region_mut), but only iterates over the part
+    /// of the region within the grid, which may be nothing.
+    pub fn region_mut_clamped<X, Y>(&mut self, xs: X, ys: Y) -> impl Iterator<Item = &mut T>
+    where
+        X: RangeBounds<usize>,
+        Y: RangeBounds<usize>,
+    {
+        let xs = Self::clamp(xs, self.width);
+        let ys = Self::clamp(ys, self.height);
+
+        self.rows_mut_in(xs, ys)
+    }
+
+    /// Like [region](Self::region), but only iterates over the part of the
+    /// region within the grid, which may be nothing.
+    pub fn region_clamped<X, Y>(&self, xs: X, ys: Y) -> impl Iterator<Item = &T>
+    where
+        X: RangeBounds<usize>,
+        Y: RangeBounds<usize>,
+    {
+        let xs = Self::clamp(xs, self.width);
+        let ys = Self::clamp(ys, self.height);
+
+        self.rows_in(xs, ys)
+    }
+
+    /// Creates a grid of the same size, converting each cell with `f`.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where F: FnMut(&T) -> U
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Rotates by 90° clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
+    }
+
+    /// Rotates by 90° counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.height, self.width, |x, y| self[(self.width - 1 - y, x)].clone())
+    }
+
+    /// Mirrors each row, swapping the left and right of the grid.
+    pub fn flip_horizontal(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.width, self.height, |x, y| self[(self.width - 1 - x, y)].clone())
+    }
+
+    /// Mirrors each column, swapping the top and bottom of the grid.
+    pub fn flip_vertical(&self) -> Self
+    where T: Clone
+    {
+        Self::from_fn(self.width, self.height, |x, y| self[(x, self.height - 1 - y)].clone())
+    }
+
+    /// Displays the grid as a character map, converting each cell with `f`.
+    pub fn display_with<F>(&self, f: F) -> DisplayWith<'_, T, F>
+    where F: Fn(&T) -> char
+    {
+        DisplayWith { grid: self, f }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        match self.index_of(x, y) {
+            Some(i) => &self.cells[i],
+            None => panic!("position ({}, {}) out of bounds for grid of size {}x{}", x, y, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        match self.index_of(x, y) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("position ({}, {}) out of bounds for grid of size {}x{}", x, y, self.width, self.height),
+        }
+    }
+}
+
+/// Rows of cells, with no separator between cells.
+impl<T> std::fmt::Display for Grid<T>
+where T: std::fmt::Display
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Character map of a grid, created by [Grid::display_with].
+pub struct DisplayWith<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<T, F> std::fmt::Display for DisplayWith<'_, T, F>
+where F: Fn(&T) -> char
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid
+            .map(|cell| (self.f)(cell))
+            .fmt(f)
+    }
+}
+
+/// Grid of bytes, with one row per line.
+impl<'a> FromInput<'a> for Grid<u8> {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, crate::error::Error> {
+        let input = <&str>::from_input(input, None)?;
+
+        Grid::parse(input, |b| b).map_err(crate::error::Error::from_error)
+    }
+}
+
+/// Grid of characters, with one row per line.
+impl<'a> FromInput<'a> for Grid<char> {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, crate::error::Error> {
+        let rows = <&str>::from_input(input, None)?
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect::<Vec<Vec<char>>>();
+
+        Grid::from_cells(rows).map_err(crate::error::Error::from_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", |b| b as char).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        assert_eq!(Grid::from_rows(&[&b"ab"[..], b"c"], |b| b), Err(Error::RaggedRow { row: 1, expected: 2, found: 1 }));
+        assert!(Grid::parse("", |b| b).unwrap().is_empty());
+    }
+
+    #[test]
+    fn access() {
+        let grid = grid();
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(1, 1), Some(&'e'));
+        assert_eq!(grid.get_wrapping(-1, 2), &'c');
+        assert_eq!(grid.get_wrapping(4, -1), &'e');
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), ["ad", "be", "cf"]);
+        assert_eq!(grid.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.count(|&c| c > 'b'), 4);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = grid();
+        assert_eq!(grid.neighbors4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4(1, 1).collect::<Vec<_>>(), [(1, 0), (0, 1), (2, 1)]);
+        assert_eq!(grid.neighbors8(1, 1).collect::<Vec<_>>(), [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn regions() {
+        let mut grid = Grid::new(4, 3, 0);
+        grid.region_mut(1..=2, 0..=1).for_each(|n| *n += 1);
+        grid.region_mut(2.., 1..).for_each(|n| *n += 2);
+        assert_eq!(grid.to_string(), "0110\n0132\n0022");
+        assert_eq!(grid.region(.., 1..=1).sum::<i32>(), 6);
+
+        grid.region_mut_clamped(3..=10, 2..).for_each(|n| *n += 1);
+        grid.region_mut_clamped(4..10, 0..3).for_each(|n| *n += 1);
+        let (start, end) = (2, 1);
+        grid.region_mut_clamped(start..=end, ..).for_each(|n| *n += 1);
+        assert_eq!(grid.to_string(), "0110\n0132\n0023");
+        assert_eq!(grid.region_clamped(1.., 1..=usize::MAX).sum::<i32>(), 11);
+        assert_eq!(grid.region_clamped(5.., ..).count(), 0);
+    }
+
+    #[test]
+    fn empty_rows() {
+        let mut grid: Grid<u8> = Grid::new(0, 3, 0);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.to_string(), "\n\n");
+        assert_eq!(grid.region_mut(.., 1..).count(), 0);
+        assert_eq!(grid.region(.., ..).count(), 0);
+    }
+
+    #[test]
+    fn char_grid_from_input() {
+        let grid = Grid::<char>::from_input("aé\n→b\n".as_bytes(), None).unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[(1, 0)], 'é');
+        assert_eq!(grid.to_string(), "aé\n→b");
+
+        assert!(Grid::<char>::from_input("ab\né\n".as_bytes(), None).is_err());
+        assert!(Grid::<char>::from_input(&[b'a', 0xFF], None).is_err());
+        assert_eq!(Grid::from_cells(vec![vec![1], vec![2, 3]]), Err(Error::RaggedRow { row: 1, expected: 1, found: 2 }));
+    }
+
+    #[test]
+    fn transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+
+        let lights = Grid::parse("#.\n.#", |b| b == b'#').unwrap();
+        assert_eq!(lights.display_with(|&on| if on { '#' } else { '.' }).to_string(), "#.\n.#");
+    }
+}
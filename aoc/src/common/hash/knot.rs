@@ -0,0 +1,112 @@
+//! The knot hash from Advent of Code 2017, days 10 and 14.
+
+use super::Hasher;
+
+pub type Digest = super::Digest<16>;
+
+/// Lengths added to the end of every message.
+const SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+
+/// Ties `rounds` rounds of knots in a list of the numbers `0..N`: for each
+/// length, reverses that many elements (wrapping around the end of the
+/// list) from the current position, then moves forwards by the length plus
+/// a skip size that grows by one each time. Lengths longer than the list
+/// are skipped.
+///
+/// `N` must be between 1 and 256, so that the numbers fit in a `u8`; other
+/// sizes fail to compile.
+pub fn sparse_hash<const N: usize>(lengths: &[u8], rounds: usize) -> [u8; N] {
+    const { assert!(N > 0 && N <= 256, "knot hash lists hold 1 to 256 numbers") };
+
+    let mut list: [u8; N] = std::array::from_fn(|i| i as u8);
+    let mut position = 0;
+    let mut skip = 0;
+
+    for _ in 0..rounds {
+        for &length in lengths {
+            let length = length as usize;
+            if length > N {
+                continue;
+            }
+
+            for i in 0..length / 2 {
+                list.swap((position + i) % N, (position + length - 1 - i) % N);
+            }
+
+            position = (position + length + skip) % N;
+            skip += 1;
+        }
+    }
+
+    list
+}
+
+/// Knot hash of a message. The whole message is needed before any rounds
+/// can be run, so updates just collect it.
+#[derive(Clone, Default)]
+pub struct State {
+    lengths: Vec<u8>,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Hasher for State {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where T: AsRef<[u8]>
+    {
+        self.lengths.extend_from_slice(data.as_ref());
+    }
+
+    fn digest(mut self) -> Digest {
+        self.lengths.extend(SUFFIX);
+        let sparse: [u8; 256] = sparse_hash(&self.lengths, 64);
+
+        let mut dense = [0u8; 16];
+        for (byte, block) in dense.iter_mut().zip(sparse.chunks_exact(16)) {
+            *byte = block.iter().fold(0, |acc, &n| acc ^ n);
+        }
+
+        Digest::from(dense)
+    }
+}
+
+pub fn knot_hash<T: AsRef<[u8]>>(data: T) -> Digest {
+    State::hash(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_hash() {
+        // The example from 2017 day 10, on a list of five numbers.
+        assert_eq!(sparse_hash::<5>(&[3, 4, 1, 5], 1), [3, 4, 2, 1, 0]);
+        assert_eq!(sparse_hash::<5>(&[3, 6, 4, 1, 5], 1), [3, 4, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_knot_hash() {
+        let vectors = [
+            ("", "a2582a3a0e66e6e86e3812dcb672a272"),
+            ("AoC 2017", "33efeb34ea91902bb2f59c9920caa6cd"),
+            ("1,2,3", "3efbe78a8d82f29979031a4aa0b16a9d"),
+            ("1,2,4", "63960835bcdc130f0b66d7ff4f6a5a8e"),
+        ];
+
+        for (input, output) in vectors {
+            assert_eq!(knot_hash(input), output, "{:?}", input);
+        }
+
+        let mut state = State::new();
+        state.update("AoC");
+        state.update(" 2017");
+        assert_eq!(state.digest(), "33efeb34ea91902bb2f59c9920caa6cd");
+    }
+}
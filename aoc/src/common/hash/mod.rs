@@ -0,0 +1,182 @@
+pub mod knot;
+pub mod sha1;
+pub mod sha256;
+
+/// An incremental hash function, such as [md5](super::md5::State),
+/// [sha1::State], [sha256::State] or [knot::State].
+pub trait Hasher: Clone + Default {
+    type Digest: AsRef<[u8]> + std::fmt::LowerHex + std::fmt::UpperHex + PartialEq<str>;
+
+    /// Adds `data` to the message.
+    fn update<T>(&mut self, data: T)
+    where T: AsRef<[u8]>;
+
+    /// Finishes the message and returns its hash.
+    fn digest(self) -> Self::Digest;
+
+    /// Hashes a whole message at once.
+    fn hash<T>(data: T) -> Self::Digest
+    where T: AsRef<[u8]>
+    {
+        let mut hasher = Self::default();
+        hasher.update(data);
+        hasher.digest()
+    }
+}
+
+/// The `N` byte output of a hash function. Formats as hex with `{:x}` or
+/// `{:X}`, and compares equal to strings holding the same hex digits, in
+/// either case.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Digest<const N: usize>([u8; N]);
+
+impl<const N: usize> From<[u8; N]> for Digest<N> {
+    #[inline]
+    fn from(value: [u8; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl<const N: usize> From<Digest<N>> for [u8; N] {
+    #[inline]
+    fn from(value: Digest<N>) -> Self {
+        value.0
+    }
+}
+
+impl<const N: usize> std::ops::Deref for Digest<N> {
+    type Target = [u8; N];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const N: usize> std::ops::DerefMut for Digest<N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Digest<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for Digest<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(self, f)
+    }
+}
+
+macro_rules! implement_hex_fmt {
+    ($type:ident, $format:expr) => {
+        impl<const N: usize> std::fmt::$type for Digest<N> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                for byte in &self.0 {
+                    write!(f, $format, byte)?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+implement_hex_fmt!(UpperHex, "{:02X}");
+implement_hex_fmt!(LowerHex, "{:02x}");
+
+fn hex_value(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|digit| digit as u8)
+}
+
+impl<const N: usize> PartialEq<str> for Digest<N> {
+    fn eq(&self, other: &str) -> bool {
+        other.len() == 2 * N
+            && self.0.iter().zip(other.as_bytes().chunks_exact(2)).all(|(&byte, hex)| {
+                hex_value(hex[0]) == Some(byte >> 4) && hex_value(hex[1]) == Some(byte & 0x0f)
+            })
+    }
+}
+
+macro_rules! implement_hex_eq {
+    ($($type:ty),+) => {
+        $(
+            impl<const N: usize> PartialEq<$type> for Digest<N> {
+                fn eq(&self, other: &$type) -> bool {
+                    self == AsRef::<str>::as_ref(other)
+                }
+            }
+
+            impl<const N: usize> PartialEq<Digest<N>> for $type {
+                fn eq(&self, other: &Digest<N>) -> bool {
+                    other == AsRef::<str>::as_ref(self)
+                }
+            }
+        )+
+    };
+}
+
+implement_hex_eq!(&str, String);
+
+impl<const N: usize> PartialEq<Digest<N>> for str {
+    fn eq(&self, other: &Digest<N>) -> bool {
+        other == self
+    }
+}
+
+/// Feeds `data` through a 64 byte block buffer, compressing each block as
+/// it fills up. `length` counts every byte so far, and so where the next
+/// one goes in the buffer.
+fn update_blocks<F>(buffer: &mut [u8; 64], length: &mut u64, data: &[u8], mut compress: F)
+where F: FnMut(&[u8; 64])
+{
+    for &byte in data {
+        buffer[(*length % 64) as usize] = byte;
+        *length = length.wrapping_add(1);
+
+        if length.is_multiple_of(64) {
+            compress(buffer);
+        }
+    }
+}
+
+/// Appends the padding used by the SHA family: a `1` bit, zeros, and the
+/// length of the message in bits as a big endian number, ending a block.
+fn pad_big_endian<F>(buffer: &mut [u8; 64], length: &mut u64, mut compress: F)
+where F: FnMut(&[u8; 64])
+{
+    let bit_length = length.wrapping_mul(8).to_be_bytes();
+    let zeros = (119 - (*length % 64) as usize) % 64;
+
+    update_blocks(buffer, length, &[0x80], &mut compress);
+    update_blocks(buffer, length, &[0; 64][..zeros], &mut compress);
+    update_blocks(buffer, length, &bit_length, &mut compress);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_hex() {
+        let digest = Digest::from([0x00, 0x1f, 0xa0, 0xff]);
+        assert_eq!(format!("{:x}", digest), "001fa0ff");
+        assert_eq!(format!("{:X}", digest), "001FA0FF");
+        assert_eq!(format!("{:?}", digest), "001fa0ff");
+
+        assert_eq!(digest, "001fa0ff");
+        assert_eq!(digest, "001FA0ff");
+        assert_eq!(digest, String::from("001fa0ff"));
+        assert_eq!("001fa0ff", digest);
+        assert_eq!(*"001fa0ff", digest);
+        assert_ne!(digest, "001fa0fe");
+        assert_ne!(digest, "001fa0f");
+        assert_ne!(digest, "001fa0ff00");
+        assert_ne!(digest, "001fa0fg");
+        assert_ne!(digest, "+01fa0ff");
+    }
+}
@@ -0,0 +1,114 @@
+use super::{pad_big_endian, update_blocks, Hasher};
+
+pub type Digest = super::Digest<20>;
+
+fn compress(state: &mut [u32; 5], block: &[u8; 64]) {
+    let mut w = [0u32; 80];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (t, &word) in w.iter().enumerate() {
+        let (f, k) = match t {
+            0..=19 => ((b & c) | (!b & d), 0x5A827999),
+            20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+            _ => (b ^ c ^ d, 0xCA62C1D6),
+        };
+
+        let temp = a.rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (state, word) in state.iter_mut().zip([a, b, c, d, e]) {
+        *state = state.wrapping_add(word);
+    }
+}
+
+#[derive(Clone)]
+pub struct State {
+    state: [u32; 5],
+    length: u64,
+    buffer: [u8; 64],
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self {
+            state: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+            length: 0,
+            buffer: [0; 64],
+        }
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for State {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where T: AsRef<[u8]>
+    {
+        let state = &mut self.state;
+        update_blocks(&mut self.buffer, &mut self.length, data.as_ref(), |block| compress(state, block));
+    }
+
+    fn digest(mut self) -> Digest {
+        let state = &mut self.state;
+        pad_big_endian(&mut self.buffer, &mut self.length, |block| compress(state, block));
+
+        let mut digest = [0u8; 20];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+
+        Digest::from(digest)
+    }
+}
+
+pub fn sha1<T: AsRef<[u8]>>(data: T) -> Digest {
+    State::hash(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha1() {
+        let vectors = [
+            ("", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+            ("abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+            ("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "84983e441c3bd26ebaae4aa1f95129e5e54670f1"),
+            ("The quick brown fox jumps over the lazy dog", "2fd4e1c67a2d28fced849ee1bb76e7391b93eb12"),
+        ];
+
+        for (input, output) in vectors {
+            assert_eq!(sha1(input), output, "{:?}", input);
+        }
+
+        let mut state = State::new();
+        for _ in 0..1000 {
+            state.update([b'a'; 1000]);
+        }
+        assert_eq!(state.digest(), "34aa973cd4c4daa4f61eeb2bdbad27316534016f");
+    }
+}
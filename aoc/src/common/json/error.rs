@@ -1,42 +1,82 @@
 use std::ops::Range;
 
-#[derive(Debug, Eq, PartialEq)]
-pub enum Error {
-    InvalidObjectKey,
-    LeftOverInput(Range<usize>),
-    ParseNumber(std::num::ParseIntError),
-    ParseString(std::string::FromUtf8Error),
-    UnbalancedBracket(char),
-    UnexpectedChar(char),
-    UnexpectedEndOfInput,
+/// Location of an error in the input. `line` and `column` start at 1, and
+/// columns count bytes rather than characters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
 }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::InvalidObjectKey => write!(f, "invalid object key")?,
-            Self::LeftOverInput(Range { start, end }) => write!(f, "left over input in range {}..{}", start, end)?,
-            Self::ParseNumber(e) => write!(f, "error parsing number: {}", e)?,
-            Self::ParseString(e) => write!(f, "error parsing string: {}", e)?,
-            Self::UnbalancedBracket(char) => write!(f, "unbalanced bracket: {}", char)?,
-            Self::UnexpectedChar(char) => write!(f, "unexpected char: {}", char)?,
-            Self::UnexpectedEndOfInput => write!(f, "unexpected end of input")?,
-        }
+impl Position {
+    pub(crate) fn new(input: &[u8], offset: usize) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line = before.iter().filter(|&&c| c == b'\n').count() + 1;
+        let column = match before.iter().rposition(|&c| c == b'\n') {
+            Some(newline) => offset - newline,
+            None => offset + 1,
+        };
 
-        Ok(())
+        Self { offset, line, column }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    DepthLimitExceeded(Position),
+    InvalidEscape(Position),
+    InvalidNumber(Range<usize>, Position),
+    InvalidObjectKey(Position),
+    InvalidUnicode(Position),
+    InvalidUtf8(Position),
+    LeftOverInput(Range<usize>, Position),
+    UnbalancedBracket(char, Position),
+    UnexpectedChar(char, Position),
+    UnexpectedEndOfInput(Position),
+}
 
-impl From<std::num::ParseIntError> for Error {
-    fn from(value: std::num::ParseIntError) -> Self {
-        Self::ParseNumber(value)
+impl Error {
+    pub fn position(&self) -> Position {
+        match *self {
+            Self::DepthLimitExceeded(position)
+            | Self::InvalidEscape(position)
+            | Self::InvalidNumber(_, position)
+            | Self::InvalidObjectKey(position)
+            | Self::InvalidUnicode(position)
+            | Self::InvalidUtf8(position)
+            | Self::LeftOverInput(_, position)
+            | Self::UnbalancedBracket(_, position)
+            | Self::UnexpectedChar(_, position)
+            | Self::UnexpectedEndOfInput(position) => position,
+        }
     }
 }
 
-impl From<std::string::FromUtf8Error> for Error {
-    fn from(value: std::string::FromUtf8Error) -> Self {
-        Self::ParseString(value)
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DepthLimitExceeded(_) => write!(f, "arrays and objects nested too deeply")?,
+            Self::InvalidEscape(_) => write!(f, "invalid escape sequence")?,
+            Self::InvalidNumber(Range { start, end }, _) => write!(f, "invalid number in range {}..{}", start, end)?,
+            Self::InvalidObjectKey(_) => write!(f, "invalid object key")?,
+            Self::InvalidUnicode(_) => write!(f, "invalid unicode escape")?,
+            Self::InvalidUtf8(_) => write!(f, "malformed UTF-8 in string")?,
+            Self::LeftOverInput(Range { start, end }, _) => write!(f, "left over input in range {}..{}", start, end)?,
+            Self::UnbalancedBracket(char, _) => write!(f, "unbalanced bracket: {}", char)?,
+            Self::UnexpectedChar(char, _) => write!(f, "unexpected char: {}", char.escape_debug())?,
+            Self::UnexpectedEndOfInput(_) => write!(f, "unexpected end of input")?,
+        }
+
+        write!(f, " at {}", self.position())
     }
 }
+
+impl std::error::Error for Error {}
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use super::Json;
+use super::number::Number;
+
+fn write_string<W: Write>(out: &mut W, s: &str) -> std::fmt::Result {
+    out.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str(r#"\""#)?,
+            '\\' => out.write_str(r"\\")?,
+            '\u{08}' => out.write_str(r"\b")?,
+            '\u{0c}' => out.write_str(r"\f")?,
+            '\n' => out.write_str(r"\n")?,
+            '\r' => out.write_str(r"\r")?,
+            '\t' => out.write_str(r"\t")?,
+            c if (c as u32) < 0x20 => write!(out, r"\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+
+    out.write_char('"')
+}
+
+fn write_number<W: Write>(out: &mut W, n: Number) -> std::fmt::Result {
+    match n {
+        Number::Integer(n) => write!(out, "{}", n),
+        // `Debug` keeps a `.0` or exponent, so the number reads back as a float.
+        Number::Float(n) if n.is_finite() => write!(out, "{:?}", n),
+        // JSON can't represent infinity or NaN.
+        Number::Float(_) => out.write_str("null"),
+    }
+}
+
+fn write_newline<W: Write>(out: &mut W, indent: Option<usize>, depth: usize) -> std::fmt::Result {
+    if let Some(indent) = indent {
+        write!(out, "\n{:width$}", "", width = indent * depth)?;
+    }
+
+    Ok(())
+}
+
+/// Writes `json` compactly, or over multiple lines if `indent` is given.
+pub(super) fn write_json<W: Write>(out: &mut W, json: &Json, indent: Option<usize>, depth: usize) -> std::fmt::Result {
+    let separator = if indent.is_some() { ": " } else { ":" };
+
+    match json {
+        Json::Null => out.write_str("null"),
+        Json::Bool(b) => write!(out, "{}", b),
+        Json::Number(n) => write_number(out, *n),
+        Json::String(s) => write_string(out, s),
+        Json::Array(arr) if arr.is_empty() => out.write_str("[]"),
+        Json::Array(arr) => {
+            out.write_char('[')?;
+            for (i, value) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_newline(out, indent, depth + 1)?;
+                write_json(out, value, indent, depth + 1)?;
+            }
+            write_newline(out, indent, depth)?;
+            out.write_char(']')
+        },
+        Json::Object(map) if map.is_empty() => out.write_str("{}"),
+        Json::Object(map) => {
+            out.write_char('{')?;
+            for (i, (key, value)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_newline(out, indent, depth + 1)?;
+                write_string(out, key)?;
+                out.write_str(separator)?;
+                write_json(out, value, indent, depth + 1)?;
+            }
+            write_newline(out, indent, depth)?;
+            out.write_char('}')
+        },
+    }
+}
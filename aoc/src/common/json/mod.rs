@@ -1,68 +1,101 @@
 pub mod error;
-pub(crate) mod scanner;
+mod format;
+mod map;
+mod number;
+#[cfg(test)]
+mod properties;
+mod query;
+mod tokenizer;
 
-use std::collections::HashMap;
+use error::{Error, Position};
 
-use error::Error;
-use scanner::{Scanner, Token};
+pub use map::Map;
+pub use number::Number;
+pub use query::{Descendants, JsonIndex, Visit};
+pub use tokenizer::{unescape, Event, Tokenizer, DEFAULT_MAX_DEPTH};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum Json {
+/// An array or object that is still being parsed, along with the key of the
+/// next object member.
+enum Partial {
     Array(Vec<Json>),
-    Object(HashMap<String, Json>),
-    Number(i64),
+    Object(Map, String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(Number),
     String(String),
+    Array(Vec<Json>),
+    Object(Map),
 }
 
 impl Json {
+    /// Parses a JSON document as described by RFC 8259, nested at most
+    /// [DEFAULT_MAX_DEPTH] levels deep.
     pub fn parse(input: &[u8]) -> Result<Json, Error> {
-        let (token, _, _) = Scanner::scan(input)?;
-
-        Self::map_token_to_json(token)
-    }
-
-    fn map_token_to_json(token: Token) -> Result<Json, Error> {
-        match token {
-            Token::Array(tokens) => {
-                let mut arr = vec![];
-
-                for token in tokens {
-                    arr.push(Self::map_token_to_json(token)?);
-                }
-
-                Ok(Self::Array(arr))
-            },
-            Token::Object(kvs) => {
-                let mut map = HashMap::new();
-
-                for (key, value) in kvs {
-                    let key = match key {
-                        Token::String(bytes) => String::from_utf8(bytes.to_vec())?,
-                        _ => return Err(Error::InvalidObjectKey),
-                    };
-
-                    map.insert(key, Json::map_token_to_json(value)?);
-                }
-
-                Ok(Self::Object(map))
-            },
-            Token::Number(bytes) => {
-                let (sign, start) = match bytes[0] {
-                    b'-' => (-1i64, 1usize),
-                    _ => (1, 0),
-                };
-                let mut curr = 0;
-
-                for &c in &bytes[start..] {
-                    curr = (curr * 10) + ((c - b'0') as i64);
-                }
-
-                Ok(Self::Number(curr * sign))
-            },
-            Token::String(bytes) => {
-                Ok(Self::String(String::from_utf8(bytes.to_vec())?))
-            },
+        let mut tokenizer = Tokenizer::new(input);
+        let mut stack = vec![];
+
+        while let Some(event) = tokenizer.next() {
+            let json = match event? {
+                Event::StartArray => {
+                    stack.push(Partial::Array(vec![]));
+                    continue;
+                },
+                Event::StartObject => {
+                    stack.push(Partial::Object(Map::new(), String::new()));
+                    continue;
+                },
+                Event::Key(raw) => {
+                    if let Some(Partial::Object(_, key)) = stack.last_mut() {
+                        *key = unescape(raw)?.into_owned();
+                    }
+                    continue;
+                },
+                Event::EndArray | Event::EndObject => match stack.pop() {
+                    Some(Partial::Array(arr)) => Json::Array(arr),
+                    Some(Partial::Object(map, _)) => Json::Object(map),
+                    None => continue,
+                },
+                Event::String(raw) => Json::String(unescape(raw)?.into_owned()),
+                Event::Number(raw) => match Number::parse(raw) {
+                    Some(n) => Json::Number(n),
+                    None => {
+                        let start = tokenizer.offset() - raw.len();
+                        return Err(Error::InvalidNumber(start..tokenizer.offset(), Position::new(input, start)));
+                    },
+                },
+                Event::Bool(b) => Json::Bool(b),
+                Event::Null => Json::Null,
+            };
+
+            match stack.last_mut() {
+                None => return tokenizer.try_fold(json, |json, event| event.map(|_| json)),
+                Some(Partial::Array(arr)) => arr.push(json),
+                Some(Partial::Object(map, key)) => { map.insert(std::mem::take(key), json); },
+            }
         }
+
+        Err(Error::UnexpectedEndOfInput(Position::new(input, input.len())))
+    }
+
+    /// Formats with each array element and object member on its own line,
+    /// indented by `indent` spaces per level of nesting.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        // Writing to a `String` can't fail.
+        let _ = format::write_json(&mut out, self, Some(indent), 0);
+
+        out
+    }
+}
+
+/// Formats as compact JSON, without any whitespace.
+impl std::fmt::Display for Json {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format::write_json(f, self, None, 0)
     }
 }
 
@@ -70,7 +103,7 @@ macro_rules! impl_json_number {
     (__impl $ty:ty) => {
         impl From<$ty> for Json {
             fn from(value: $ty) -> Self {
-                Json::Number(value as i64)
+                Json::Number(Number::Integer(value as i64))
             }
         }
     };
@@ -86,6 +119,52 @@ macro_rules! impl_json_number {
 impl_json_number!(isize, i8, i16, i32, i64, i128);
 impl_json_number!(usize, u8, u16, u32, u64, u128);
 
+impl From<f32> for Json {
+    fn from(value: f32) -> Self {
+        Json::Number(Number::Float(value as f64))
+    }
+}
+
+impl From<f64> for Json {
+    fn from(value: f64) -> Self {
+        Json::Number(Number::Float(value))
+    }
+}
+
+impl From<Number> for Json {
+    fn from(value: Number) -> Self {
+        Json::Number(value)
+    }
+}
+
+impl From<bool> for Json {
+    fn from(value: bool) -> Self {
+        Json::Bool(value)
+    }
+}
+
+impl From<Map> for Json {
+    fn from(value: Map) -> Self {
+        Json::Object(value)
+    }
+}
+
+impl<T> From<Vec<T>> for Json
+where T: Into<Json>
+{
+    fn from(value: Vec<T>) -> Self {
+        Json::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> From<Option<T>> for Json
+where T: Into<Json>
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Json::Null, Into::into)
+    }
+}
+
 macro_rules! impl_json_string {
     (__impl $ty:ty) => {
         impl From<$ty> for Json {
@@ -108,57 +187,55 @@ impl_json_string!(String, &str, &String);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use error::Position;
 
     #[test]
     fn json_parse() {
-        let _json = Json::Array(vec![
-            Json::Number(1),
-            Json::Number(2),
-            Json::Number(3),
-        ]);
-
-        let mut map = HashMap::new();
-        map.insert(String::from("a"), Json::Number(2));
-        map.insert(String::from("b"), Json::Number(4));
-        let _json = Json::Object(map);
-
-        let _json = Json::Array(vec![
-            Json::Array(vec![
-                Json::Array(vec![
-                    Json::Number(3),
-                ]),
-            ]),
-        ]);
+        let json = Json::parse(b"[1,2,3]");
+        assert_eq!(json, Ok(Json::Array(vec![
+            Json::from(1),
+            Json::from(2),
+            Json::from(3),
+        ])));
+
+        let json = Json::parse(br#"{"a":2,"b":4}"#);
+        let mut map = Map::new();
+        map.insert(String::from("a"), Json::from(2));
+        map.insert(String::from("b"), Json::from(4));
+        assert_eq!(json, Ok(Json::Object(map)));
 
-        let mut map1 = HashMap::new();
-        let mut map2 = HashMap::new();
-        map2.insert(String::from("b"), Json::Number(4));
+        let json = Json::parse(br#"{"a":{"b":4},"c":-1}"#);
+        let mut map1 = Map::new();
+        let mut map2 = Map::new();
+        map2.insert(String::from("b"), Json::from(4));
         map1.insert(String::from("a"), Json::Object(map2));
-        map1.insert(String::from("c"), Json::Number(-1));
-        let _json = Json::Object(map1);
+        map1.insert(String::from("c"), Json::from(-1));
+        assert_eq!(json, Ok(Json::Object(map1)));
 
-        let mut map = HashMap::new();
+        let json = Json::parse(br#"{"a":[-1,1]}"#);
+        let mut map = Map::new();
         map.insert(String::from("a"), Json::Array(vec![
-            Json::Number(-1),
-            Json::Number(1),
+            Json::from(-1),
+            Json::from(1),
         ]));
-        let _json = Json::Object(map);
+        assert_eq!(json, Ok(Json::Object(map)));
 
-        let mut map = HashMap::new();
-        map.insert(String::from("a"), Json::Number(1));
-        let _json = Json::Array(vec![
-            Json::Number(-1),
+        let json = Json::parse(br#"[-1,{"a":1}]"#);
+        let mut map = Map::new();
+        map.insert(String::from("a"), Json::from(1));
+        assert_eq!(json, Ok(Json::Array(vec![
+            Json::from(-1),
             Json::Object(map),
-        ]);
+        ])));
     }
 
     #[test]
     fn json_parse_number() {
         let json = Json::parse(b"123");
-        assert_eq!(json, Ok(Json::Number(123)));
+        assert_eq!(json, Ok(Json::from(123)));
 
         let json = Json::parse(b"-91");
-        assert_eq!(json, Ok(Json::Number(-91)));
+        assert_eq!(json, Ok(Json::from(-91)));
     }
 
     #[test]
@@ -180,7 +257,7 @@ mod tests {
             Json::Array(vec![
                 Json::Array(vec![
                     Json::Array(vec![
-                        Json::Number(3),
+                        Json::from(3),
                     ]),
                 ]),
             ])));
@@ -189,8 +266,135 @@ mod tests {
     #[test]
     fn json_parse_object() {
         let json = Json::parse(br#"{"red":[200]}"#);
-        let mut map = HashMap::new();
-        map.insert(String::from("red"), Json::Array(vec![Json::Number(200)]));
+        let mut map = Map::new();
+        map.insert(String::from("red"), Json::Array(vec![Json::from(200)]));
+        assert_eq!(json, Ok(Json::Object(map)));
+    }
+
+    #[test]
+    fn json_parse_literals() {
+        assert_eq!(Json::parse(b"true"), Ok(Json::Bool(true)));
+        assert_eq!(Json::parse(b"false"), Ok(Json::Bool(false)));
+        assert_eq!(Json::parse(b"null"), Ok(Json::Null));
+        assert_eq!(Json::parse(b"[null, true]"), Ok(Json::Array(vec![Json::Null, Json::from(true)])));
+        assert!(matches!(Json::parse(b"nul"), Err(Error::UnexpectedEndOfInput(_))));
+        assert!(matches!(Json::parse(b"tru3"), Err(Error::UnexpectedChar('3', _))));
+    }
+
+    #[test]
+    fn json_parse_float() {
+        assert_eq!(Json::parse(b"0"), Ok(Json::from(0)));
+        assert_eq!(Json::parse(b"-0.5"), Ok(Json::from(-0.5)));
+        assert_eq!(Json::parse(b"1.25e2"), Ok(Json::from(125.0)));
+        assert_eq!(Json::parse(b"2E-2"), Ok(Json::from(0.02)));
+        assert_eq!(Json::parse(b"9223372036854775807"), Ok(Json::from(i64::MAX)));
+        assert_eq!(Json::parse(b"9223372036854775808"), Ok(Json::from(9223372036854775808.0)));
+
+        for invalid in [&b"-"[..], b"1.", b"1e", b"-a", b"1e999"] {
+            assert!(matches!(Json::parse(invalid), Err(Error::InvalidNumber(..))), "{:?}", invalid);
+        }
+        assert!(matches!(Json::parse(b".5"), Err(Error::UnexpectedChar('.', _))));
+        assert!(matches!(Json::parse(b"01"), Err(Error::LeftOverInput(..))));
+    }
+
+    #[test]
+    fn json_parse_escapes() {
+        let json = Json::parse(br#""\"\\\/\b\f\n\r\t""#);
+        assert_eq!(json, Ok(Json::from("\"\\/\u{08}\u{0c}\n\r\t")));
+
+        let json = Json::parse(br#""caf\u00e9 \u20AC \ud83d\uDE00 []{}""#);
+        assert_eq!(json, Ok(Json::from("café € 😀 []{}")));
+
+        let json = Json::parse("\"unescaped ünïcödé\"".as_bytes());
+        assert_eq!(json, Ok(Json::from("unescaped ünïcödé")));
+
+        assert!(matches!(Json::parse(br#""\x""#), Err(Error::InvalidEscape(_))));
+        assert!(matches!(Json::parse(br#""\u12g4""#), Err(Error::InvalidEscape(_))));
+        assert!(matches!(Json::parse(br#""\ud83d""#), Err(Error::InvalidUnicode(_))));
+        assert!(matches!(Json::parse(br#""\ud83dA""#), Err(Error::InvalidUnicode(_))));
+        assert!(matches!(Json::parse(br#""\ude00""#), Err(Error::InvalidUnicode(_))));
+        assert!(matches!(Json::parse(b"\"\xff\""), Err(Error::InvalidUtf8(_))));
+        assert!(matches!(Json::parse(b"\"a\nb\""), Err(Error::UnexpectedChar('\n', _))));
+        assert!(matches!(Json::parse(br#""abc"#), Err(Error::UnexpectedEndOfInput(_))));
+    }
+
+    #[test]
+    fn json_parse_whitespace() {
+        let json = Json::parse(b" \r\n\t{ \"a\" : [ 1 , 2 ] ,\n \"b\" :{ } } \n");
+        let mut map = Map::new();
+        map.insert(String::from("a"), Json::Array(vec![Json::from(1), Json::from(2)]));
+        map.insert(String::from("b"), Json::Object(Map::new()));
         assert_eq!(json, Ok(Json::Object(map)));
+
+        assert_eq!(Json::parse(b" [ ] "), Ok(Json::Array(vec![])));
+    }
+
+    #[test]
+    fn json_parse_errors() {
+        let error = Json::parse(b"[1,\n  2,\n  x]").unwrap_err();
+        assert_eq!(error, Error::UnexpectedChar('x', Position { offset: 11, line: 3, column: 3 }));
+        assert_eq!(error.to_string(), "unexpected char: x at line 3, column 3");
+
+        let error = Json::parse(b"[1, 2]]").unwrap_err();
+        assert_eq!(error, Error::UnbalancedBracket(']', Position { offset: 6, line: 1, column: 7 }));
+
+        assert!(matches!(Json::parse(b"[1}"), Err(Error::UnbalancedBracket('}', _))));
+        assert!(matches!(Json::parse(b"}"), Err(Error::UnbalancedBracket('}', _))));
+        assert!(matches!(Json::parse(b"[1,]"), Err(Error::UnexpectedChar(']', _))));
+        assert!(matches!(Json::parse(b"[1 2]"), Err(Error::UnexpectedChar('2', _))));
+        assert!(matches!(Json::parse(b"{1:2}"), Err(Error::InvalidObjectKey(_))));
+        assert!(matches!(Json::parse(br#"{"a" 2}"#), Err(Error::UnexpectedChar('2', _))));
+        assert!(matches!(Json::parse(b"[[1]"), Err(Error::UnexpectedEndOfInput(_))));
+        assert!(matches!(Json::parse(b""), Err(Error::UnexpectedEndOfInput(_))));
+        assert!(matches!(Json::parse("é".as_bytes()), Err(Error::UnexpectedChar('é', _))));
+        assert_eq!(Json::parse(b"1 2").unwrap_err(), Error::LeftOverInput(2..3, Position { offset: 2, line: 1, column: 3 }));
+    }
+
+    #[test]
+    fn json_display() {
+        let json = Json::parse(br#" { "b" : [1, -2.5, 1e100, true, null], "a": {}, "c": [], "d": {"e": "f"} } "#).unwrap();
+        assert_eq!(json.to_string(), r#"{"b":[1,-2.5,1e100,true,null],"a":{},"c":[],"d":{"e":"f"}}"#);
+        assert_eq!(json.to_string_pretty(2), [
+            "{",
+            r#"  "b": ["#,
+            "    1,",
+            "    -2.5,",
+            "    1e100,",
+            "    true,",
+            "    null",
+            "  ],",
+            r#"  "a": {},"#,
+            r#"  "c": [],"#,
+            r#"  "d": {"#,
+            r#"    "e": "f""#,
+            "  }",
+            "}",
+        ].join("\n"));
+
+        assert_eq!(Json::from("\"\\/\u{08}\u{0c}\n\r\t\u{1} é").to_string(), r#""\"\\/\b\f\n\r\t\u0001 é""#);
+        assert_eq!(Json::from(3.0).to_string(), "3.0");
+        assert_eq!(Json::from(f64::NAN).to_string(), "null");
+        assert_eq!(Json::from(Some(1)).to_string_pretty(4), "1");
+        assert_eq!(Json::from(vec![None, Some("a")]).to_string(), r#"[null,"a"]"#);
+
+        for json in [json, Json::from(3.0), Json::from(0.1), Json::from(-1e-7), Json::from("\u{1}")] {
+            assert_eq!(Json::parse(json.to_string().as_bytes()), Ok(json.clone()));
+            assert_eq!(Json::parse(json.to_string_pretty(3).as_bytes()), Ok(json));
+        }
+    }
+
+    #[test]
+    fn json_map() {
+        let mut map: Map = [("z", 1), ("a", 2), ("m", 3)].into_iter().collect();
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["z", "a", "m"]);
+        assert_eq!(map.insert("a", 4), Some(Json::from(2)));
+        assert_eq!(map.get("a"), Some(&Json::from(4)));
+        assert_eq!(map.remove("z"), Some(Json::from(1)));
+        assert_eq!(map.get("m"), Some(&Json::from(3)));
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "m"]);
+
+        let reordered: Map = [("m", 3), ("a", 4)].into_iter().collect();
+        assert_eq!(map, reordered);
+        assert_ne!(map, Map::new());
     }
 }
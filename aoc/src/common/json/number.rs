@@ -0,0 +1,54 @@
+/// A JSON number. Numbers without a fraction or exponent that fit in an
+/// `i64` are kept as integers, and everything else becomes a float.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    /// Converts the raw bytes of a number, as produced by the
+    /// [Tokenizer](super::Tokenizer). Returns `None` if they aren't a number,
+    /// or if it is too large to represent.
+    pub fn parse(raw: &[u8]) -> Option<Number> {
+        let text = std::str::from_utf8(raw).ok()?;
+
+        if !text.contains(['.', 'e', 'E']) {
+            if let Ok(n) = text.parse::<i64>() {
+                return Some(Number::Integer(n));
+            }
+        }
+
+        text.parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .map(Number::Float)
+    }
+
+    /// Returns the value if this is an integer.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Self::Integer(n) => Some(n),
+            Self::Float(_) => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Self::Integer(n) => n as f64,
+            Self::Float(n) => n,
+        }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for Number {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
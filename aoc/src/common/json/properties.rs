@@ -0,0 +1,113 @@
+//! Property tests for the parser: arbitrary input must never panic, and
+//! generated documents must survive a round trip through the formatter.
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use super::{Event, Json, Map, Number, Tokenizer};
+use super::error::{Error, Position};
+
+fn arb_number() -> impl Strategy<Value = Number> {
+    prop_oneof![
+        any::<i64>().prop_map(Number::Integer),
+        // Non-finite floats are written as `null`, so they can't round trip.
+        any::<f64>().prop_filter("finite", |n| n.is_finite()).prop_map(Number::Float),
+    ]
+}
+
+fn arb_json() -> impl Strategy<Value = Json> {
+    let leaf = prop_oneof![
+        Just(Json::Null),
+        any::<bool>().prop_map(Json::Bool),
+        arb_number().prop_map(Json::Number),
+        any::<String>().prop_map(Json::String),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| prop_oneof![
+        vec(inner.clone(), 0..8).prop_map(Json::Array),
+        vec((any::<String>(), inner), 0..8).prop_map(|members| Json::Object(members.into_iter().collect::<Map>())),
+    ])
+}
+
+/// Bytes that are mostly JSON punctuation and literals, which get much
+/// further into the parser than uniformly random bytes.
+fn arb_jsonish() -> impl Strategy<Value = Vec<u8>> {
+    const TOKENS: [&[u8]; 19] = [
+        b"[", b"]", b"{", b"}", b",", b":", b" ", b"\"", b"\\", b"\\u", b"d83d",
+        b"0", b"-1", b".5", b"e+", b"true", b"nul", b"\xc3", b"\xa9",
+    ];
+
+    vec(prop::sample::select(&TOKENS[..]), 0..32).prop_map(|tokens| tokens.concat())
+}
+
+/// The parser either succeeds, or fails at a position inside the input, and
+/// the tokenizer agrees with it.
+fn check_input(input: &[u8]) -> Result<(), TestCaseError> {
+    let parsed = Json::parse(input);
+    let tokenized: Result<Vec<Event<'_>>, Error> = Tokenizer::new(input).collect();
+    prop_assert_eq!(parsed.is_ok(), tokenized.is_ok());
+
+    match parsed {
+        Ok(json) => prop_assert_eq!(Json::parse(json.to_string().as_bytes()), Ok(json)),
+        Err(error) => prop_assert!(error.position().offset <= input.len()),
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn parse_arbitrary_bytes(input in vec(any::<u8>(), 0..256)) {
+        check_input(&input)?;
+    }
+
+    #[test]
+    fn parse_jsonish_bytes(input in arb_jsonish()) {
+        check_input(&input)?;
+    }
+
+    #[test]
+    fn round_trip(json in arb_json(), indent in 0..4usize) {
+        prop_assert_eq!(Json::parse(json.to_string().as_bytes()), Ok(json.clone()));
+        prop_assert_eq!(Json::parse(json.to_string_pretty(indent).as_bytes()), Ok(json));
+    }
+
+    #[test]
+    fn truncated_containers(values in vec(arb_json(), 0..4), cut in any::<prop::sample::Index>()) {
+        let text = Json::Array(values).to_string();
+        let cut = cut.index(text.len());
+
+        let error = Json::parse(&text.as_bytes()[..cut]).unwrap_err();
+        prop_assert!(error.position().offset <= cut);
+    }
+
+    #[test]
+    fn unbalanced_brackets(json in arb_json(), close in prop_oneof![Just(']'), Just('}')]) {
+        let text = format!("{}{}", json, close);
+        let offset = text.len() - 1;
+        prop_assert_eq!(
+            Json::parse(text.as_bytes()),
+            Err(Error::UnbalancedBracket(close, Position::new(text.as_bytes(), offset)))
+        );
+
+        let mismatched = match close {
+            ']' => format!("{{\"a\":{}]", json),
+            _ => format!("[{}}}", json),
+        };
+        let offset = mismatched.len() - 1;
+        prop_assert_eq!(
+            Json::parse(mismatched.as_bytes()),
+            Err(Error::UnbalancedBracket(close, Position::new(mismatched.as_bytes(), offset)))
+        );
+    }
+
+    #[test]
+    fn left_over_input(json in arb_json(), space in "[ \t\r\n]{0,3}", rest in arb_json()) {
+        let text = format!("{}{} {}", json, space, rest);
+        let start = text.len() - rest.to_string().len();
+        prop_assert_eq!(
+            Json::parse(text.as_bytes()),
+            Err(Error::LeftOverInput(start..text.len(), Position::new(text.as_bytes(), start)))
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use super::{Json, Map};
+
+/// Something that can select a value inside a [Json], either an object key
+/// or an array index.
+pub trait JsonIndex {
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json>;
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json>;
+}
+
+impl JsonIndex for str {
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json> {
+        json.as_object()?.get(self)
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json> {
+        json.as_object_mut()?.get_mut(self)
+    }
+}
+
+impl JsonIndex for String {
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json> {
+        self.as_str().index_into(json)
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json> {
+        self.as_str().index_into_mut(json)
+    }
+}
+
+impl JsonIndex for usize {
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json> {
+        json.as_array()?.get(*self)
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json> {
+        json.as_array_mut()?.get_mut(*self)
+    }
+}
+
+impl<T> JsonIndex for &T
+where T: JsonIndex + ?Sized
+{
+    fn index_into<'a>(&self, json: &'a Json) -> Option<&'a Json> {
+        (**self).index_into(json)
+    }
+
+    fn index_into_mut<'a>(&self, json: &'a mut Json) -> Option<&'a mut Json> {
+        (**self).index_into_mut(json)
+    }
+}
+
+/// What [Json::visit] should do after visiting a value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Visit {
+    /// Go on to the children of this value.
+    Continue,
+    /// Skip the children of this value.
+    Prune,
+    /// Stop visiting altogether.
+    Stop,
+}
+
+/// Splits a JSON pointer into its unescaped reference tokens.
+fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = String> + '_> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+
+    Some(
+        pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+    )
+}
+
+/// Array indices in a JSON pointer are digits without leading zeros.
+fn pointer_index(token: &str) -> Option<usize> {
+    match token.as_bytes() {
+        [b'0'] => Some(0),
+        [b'1'..=b'9', rest @ ..] if rest.iter().all(u8::is_ascii_digit) => token.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Pushes the children of `json` onto a depth first traversal stack, last
+/// child first so they are popped in document order.
+fn push_children<'a>(stack: &mut Vec<&'a Json>, json: &'a Json) {
+    match json {
+        Json::Array(arr) => stack.extend(arr.iter().rev()),
+        Json::Object(map) => stack.extend(map.values().rev()),
+        _ => {},
+    }
+}
+
+impl Json {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Json::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Json::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Json::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(n.as_f64()),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&Vec<Json>> {
+        match self {
+            Json::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Json>> {
+        match self {
+            Json::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&Map> {
+        match self {
+            Json::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut Map> {
+        match self {
+            Json::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Looks up an object member by key, or an array element by index.
+    pub fn get<I: JsonIndex>(&self, index: I) -> Option<&Json> {
+        index.index_into(self)
+    }
+
+    pub fn get_mut<I: JsonIndex>(&mut self, index: I) -> Option<&mut Json> {
+        index.index_into_mut(self)
+    }
+
+    /// Looks up a value by a JSON pointer (RFC 6901), such as `/a/0/b`. The
+    /// empty pointer refers to the whole document.
+    pub fn pointer(&self, pointer: &str) -> Option<&Json> {
+        pointer_tokens(pointer)?.try_fold(self, |json, token| match json {
+            Json::Object(map) => map.get(&token),
+            Json::Array(arr) => arr.get(pointer_index(&token)?),
+            _ => None,
+        })
+    }
+
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Json> {
+        pointer_tokens(pointer)?.try_fold(self, |json, token| match json {
+            Json::Object(map) => map.get_mut(&token),
+            Json::Array(arr) => arr.get_mut(pointer_index(&token)?),
+            _ => None,
+        })
+    }
+
+    /// Every value nested inside this one, depth first, in document order.
+    pub fn descendants(&self) -> Descendants<'_> {
+        let mut stack = vec![];
+        push_children(&mut stack, self);
+
+        Descendants { stack }
+    }
+
+    /// Calls `f` with this value and every value nested in it, depth first,
+    /// in document order. The result of `f` decides whether to descend into
+    /// the children of a value.
+    pub fn visit<F>(&self, mut f: F)
+    where F: FnMut(&Json) -> Visit
+    {
+        let mut stack = vec![self];
+
+        while let Some(json) = stack.pop() {
+            match f(json) {
+                Visit::Continue => push_children(&mut stack, json),
+                Visit::Prune => {},
+                Visit::Stop => return,
+            }
+        }
+    }
+
+    /// Folds this value and every value nested in it, depth first, in
+    /// document order. Values for which `prune` returns true are skipped,
+    /// along with everything inside them.
+    pub fn fold<B, P, F>(&self, init: B, mut prune: P, mut f: F) -> B
+    where P: FnMut(&Json) -> bool,
+          F: FnMut(B, &Json) -> B,
+    {
+        let mut acc = init;
+        let mut stack = vec![self];
+
+        while let Some(json) = stack.pop() {
+            if !prune(json) {
+                acc = f(acc, json);
+                push_children(&mut stack, json);
+            }
+        }
+
+        acc
+    }
+}
+
+static NULL: Json = Json::Null;
+
+/// Indexing with a missing key or index, or into a value that isn't an
+/// object or array, gives [Json::Null] rather than panicking.
+impl<I> std::ops::Index<I> for Json
+where I: JsonIndex
+{
+    type Output = Json;
+
+    fn index(&self, index: I) -> &Json {
+        self.get(index).unwrap_or(&NULL)
+    }
+}
+
+/// Iterator over nested values, created by [Json::descendants].
+pub struct Descendants<'a> {
+    stack: Vec<&'a Json>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Json;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let json = self.stack.pop()?;
+        push_children(&mut self.stack, json);
+
+        Some(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Json {
+        Json::parse(br#"{
+            "a": [{"b": 1}, {"b": 2, "c": [3, "red"]}],
+            "a/b": 4,
+            "m~n": 5,
+            "": 6,
+            "d": {"red": "red", "e": 7}
+        }"#).unwrap()
+    }
+
+    #[test]
+    fn json_get() {
+        let json = document();
+        assert_eq!(json.get("a").and_then(|a| a.get(1)).and_then(|a| a.get("b")), Some(&Json::from(2)));
+        assert_eq!(json["a"][1]["c"][1], Json::from("red"));
+        assert_eq!(json["a"][5]["c"], Json::Null);
+        assert_eq!(json["a/b"].as_i64(), Some(4));
+        assert_eq!(json.get(0), None);
+        assert_eq!(json[&String::from("m~n")].as_f64(), Some(5.0));
+
+        let mut json = json;
+        *json.get_mut("a").and_then(|a| a.get_mut(0)).unwrap() = Json::Null;
+        assert!(json["a"][0].is_null());
+    }
+
+    #[test]
+    fn json_pointer() {
+        let mut json = document();
+        assert_eq!(json.pointer(""), Some(&json));
+        assert_eq!(json.pointer("/a/1/c/0"), Some(&Json::from(3)));
+        assert_eq!(json.pointer("/a~1b"), Some(&Json::from(4)));
+        assert_eq!(json.pointer("/m~0n"), Some(&Json::from(5)));
+        assert_eq!(json.pointer("/"), Some(&Json::from(6)));
+        assert_eq!(json.pointer("/a/01"), None);
+        assert_eq!(json.pointer("/a/-"), None);
+        assert_eq!(json.pointer("/a/2"), None);
+        assert_eq!(json.pointer("a"), None);
+        assert_eq!(json.pointer("/a/0/b/c"), None);
+
+        *json.pointer_mut("/d/e").unwrap() = Json::from(true);
+        assert_eq!(json["d"]["e"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn json_traverse() {
+        let json = document();
+        let numbers: Vec<_> = json.descendants().filter_map(Json::as_i64).collect();
+        assert_eq!(numbers, [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(json.descendants().count(), 14);
+        assert_eq!(Json::from(1).descendants().count(), 0);
+
+        let has_red = |json: &Json| {
+            json.as_object().is_some_and(|map| map.values().any(|v| v.as_str() == Some("red")))
+        };
+        let sum = json.fold(0, has_red, |sum, json| sum + json.as_i64().unwrap_or(0));
+        assert_eq!(sum, 21);
+
+        let mut strings = vec![];
+        json.visit(|json| match json {
+            Json::String(s) => {
+                strings.push(s.clone());
+                Visit::Stop
+            },
+            Json::Object(map) if map.contains_key("red") => Visit::Prune,
+            _ => Visit::Continue,
+        });
+        assert_eq!(strings, ["red"]);
+    }
+}
@@ -0,0 +1,483 @@
+use std::borrow::Cow;
+
+use super::error::{Error, Position};
+
+/// Nesting depth allowed by [Tokenizer::new].
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// A syntactic element of a JSON document, borrowed from the input.
+///
+/// Strings and keys are the raw bytes between the quotes, with escapes left
+/// in, and can be decoded with [unescape]. Numbers are the raw bytes of the
+/// number, which can be converted with [Number::parse](super::Number::parse).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event<'a> {
+    StartArray,
+    EndArray,
+    StartObject,
+    EndObject,
+    Key(&'a [u8]),
+    String(&'a [u8]),
+    Number(&'a [u8]),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Container {
+    Array,
+    Object,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    /// Expecting a value, at the start or after a `,` in an array or a `:`.
+    Value,
+    /// After `[`, expecting a value or `]`.
+    FirstValue,
+    /// After `{`, expecting a key or `}`.
+    FirstKey,
+    /// After a `,` in an object, expecting a key.
+    Key,
+    /// After a key, expecting a `:`.
+    Colon,
+    /// After a value, expecting a `,` or closing bracket, or the end of the
+    /// input if the value isn't nested.
+    AfterValue,
+    Done,
+}
+
+/// Single pass, event based JSON tokenizer.
+///
+/// Yields an [Event] for each element of the document as it is scanned,
+/// validating it along the way. Memory use only grows with the nesting
+/// depth, which is limited to guard against malicious input. After an error
+/// no more events are produced.
+pub struct Tokenizer<'a> {
+    input: &'a [u8],
+    offset: usize,
+    stack: Vec<Container>,
+    state: State,
+    max_depth: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            offset: 0,
+            stack: vec![],
+            state: State::Value,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Number of arrays and objects that are currently open.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Offset of the first byte that hasn't been scanned.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline(always)]
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.offset).copied()
+    }
+
+    /// Position of the next byte to be scanned.
+    fn here(&self) -> Position {
+        Position::new(self.input, self.offset)
+    }
+
+    fn unexpected(&self) -> Error {
+        unexpected(self.input, self.offset)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event<'a>>, Error> {
+        loop {
+            if self.state == State::Done {
+                return Ok(None);
+            }
+
+            self.skip_whitespace();
+
+            match (self.state, self.peek()) {
+                (State::FirstValue, Some(b']')) | (State::FirstKey, Some(b'}')) => return Ok(Some(self.close())),
+                (State::Value | State::FirstValue, _) => return self.scan_value().map(Some),
+                (State::FirstKey | State::Key, Some(b'"')) => {
+                    let key = self.scan_string()?;
+                    self.state = State::Colon;
+                    return Ok(Some(Event::Key(key)));
+                },
+                (State::FirstKey | State::Key, None) => return Err(self.unexpected()),
+                (State::FirstKey | State::Key, Some(_)) => return Err(Error::InvalidObjectKey(self.here())),
+                (State::Colon, Some(b':')) => {
+                    self.offset += 1;
+                    self.state = State::Value;
+                },
+                (State::Colon, _) => return Err(self.unexpected()),
+                (State::AfterValue, c) => match (self.stack.last(), c) {
+                    (None, None) => self.state = State::Done,
+                    (None, Some(c @ (b']' | b'}'))) => {
+                        return Err(Error::UnbalancedBracket(c as char, self.here()));
+                    },
+                    (None, Some(_)) => {
+                        return Err(Error::LeftOverInput(self.offset..self.input.len(), self.here()));
+                    },
+                    (Some(container), Some(b',')) => {
+                        self.offset += 1;
+                        self.state = match container {
+                            Container::Array => State::Value,
+                            Container::Object => State::Key,
+                        };
+                    },
+                    (Some(Container::Array), Some(b']')) | (Some(Container::Object), Some(b'}')) => {
+                        return Ok(Some(self.close()));
+                    },
+                    (Some(_), Some(c @ (b']' | b'}'))) => {
+                        return Err(Error::UnbalancedBracket(c as char, self.here()));
+                    },
+                    (Some(_), _) => return Err(self.unexpected()),
+                },
+                (State::Done, _) => return Ok(None),
+            }
+        }
+    }
+
+    fn open(&mut self, container: Container) -> Result<Event<'a>, Error> {
+        if self.stack.len() >= self.max_depth {
+            return Err(Error::DepthLimitExceeded(self.here()));
+        }
+
+        self.offset += 1;
+        self.stack.push(container);
+
+        Ok(match container {
+            Container::Array => {
+                self.state = State::FirstValue;
+                Event::StartArray
+            },
+            Container::Object => {
+                self.state = State::FirstKey;
+                Event::StartObject
+            },
+        })
+    }
+
+    /// Consumes the closing bracket, which must match the innermost container.
+    fn close(&mut self) -> Event<'a> {
+        self.offset += 1;
+        self.state = State::AfterValue;
+
+        match self.stack.pop() {
+            Some(Container::Object) => Event::EndObject,
+            _ => Event::EndArray,
+        }
+    }
+
+    fn scan_value(&mut self) -> Result<Event<'a>, Error> {
+        let event = match self.peek() {
+            Some(b'[') => return self.open(Container::Array),
+            Some(b'{') => return self.open(Container::Object),
+            Some(b'"') => Event::String(self.scan_string()?),
+            Some(b'-' | b'0'..=b'9') => Event::Number(self.scan_number()?),
+            Some(b't') => self.scan_literal(b"true", Event::Bool(true))?,
+            Some(b'f') => self.scan_literal(b"false", Event::Bool(false))?,
+            Some(b'n') => self.scan_literal(b"null", Event::Null)?,
+            Some(c @ (b']' | b'}')) if self.stack.is_empty() => {
+                return Err(Error::UnbalancedBracket(c as char, self.here()));
+            },
+            _ => return Err(self.unexpected()),
+        };
+
+        self.state = State::AfterValue;
+        Ok(event)
+    }
+
+    fn scan_literal(&mut self, literal: &[u8], event: Event<'a>) -> Result<Event<'a>, Error> {
+        for &c in literal {
+            if self.peek() != Some(c) {
+                return Err(self.unexpected());
+            }
+            self.offset += 1;
+        }
+
+        Ok(event)
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.offset;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.offset += 1;
+        }
+
+        self.offset - start
+    }
+
+    fn scan_number(&mut self) -> Result<&'a [u8], Error> {
+        let start = self.offset;
+        let invalid = |tokenizer: &Self| {
+            Error::InvalidNumber(start..tokenizer.offset, Position::new(tokenizer.input, start))
+        };
+
+        if self.peek() == Some(b'-') {
+            self.offset += 1;
+        }
+
+        match self.peek() {
+            Some(b'0') => self.offset += 1,
+            Some(b'1'..=b'9') => { self.skip_digits(); },
+            _ => return Err(invalid(self)),
+        }
+
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            if self.skip_digits() == 0 {
+                return Err(invalid(self));
+            }
+        }
+
+        if let Some(b'e' | b'E') = self.peek() {
+            self.offset += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.offset += 1;
+            }
+            if self.skip_digits() == 0 {
+                return Err(invalid(self));
+            }
+        }
+
+        Ok(&self.input[start..self.offset])
+    }
+
+    /// Validates a string, returning the raw bytes between the quotes.
+    fn scan_string(&mut self) -> Result<&'a [u8], Error> {
+        self.offset += 1;
+        let start = self.offset;
+        let mut run = start;
+
+        loop {
+            match self.peek() {
+                None => return Err(self.unexpected()),
+                Some(b'"') => {
+                    validate_utf8(self.input, run, self.offset)?;
+                    self.offset += 1;
+                    return Ok(&self.input[start..self.offset - 1]);
+                },
+                Some(b'\\') => {
+                    validate_utf8(self.input, run, self.offset)?;
+                    let (_, end) = decode_escape(self.input, self.offset)?;
+                    self.offset = end;
+                    run = end;
+                },
+                Some(c) if c < 0x20 => return Err(Error::UnexpectedChar(c as char, self.here())),
+                Some(_) => self.offset += 1,
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Event<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.next_event();
+        if event.is_err() {
+            self.state = State::Done;
+        }
+
+        event.transpose()
+    }
+}
+
+impl std::iter::FusedIterator for Tokenizer<'_> {}
+
+/// The (possibly multi-byte) character at `offset`.
+fn char_at(input: &[u8], offset: usize) -> char {
+    let end = (offset + 4).min(input.len());
+    let bytes = input.get(offset..end).unwrap_or_default();
+    let valid = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+    };
+
+    valid.chars().next().unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+fn unexpected(input: &[u8], offset: usize) -> Error {
+    match input.get(offset) {
+        None => Error::UnexpectedEndOfInput(Position::new(input, offset)),
+        Some(_) => Error::UnexpectedChar(char_at(input, offset), Position::new(input, offset)),
+    }
+}
+
+fn validate_utf8(input: &[u8], start: usize, end: usize) -> Result<&str, Error> {
+    std::str::from_utf8(&input[start..end])
+        .map_err(|e| Error::InvalidUtf8(Position::new(input, start + e.valid_up_to())))
+}
+
+/// Decodes the escape sequence starting with the `\` at `start`, returning
+/// the character and the offset after the sequence. A `\u` escape of a high
+/// surrogate must be followed by one of a low surrogate.
+fn decode_escape(input: &[u8], start: usize) -> Result<(char, usize), Error> {
+    let offset = start + 1;
+
+    let c = match input.get(offset) {
+        Some(b'"') => '"',
+        Some(b'\\') => '\\',
+        Some(b'/') => '/',
+        Some(b'b') => '\u{08}',
+        Some(b'f') => '\u{0c}',
+        Some(b'n') => '\n',
+        Some(b'r') => '\r',
+        Some(b't') => '\t',
+        Some(b'u') => return decode_unicode(input, start),
+        None => return Err(unexpected(input, offset)),
+        Some(_) => return Err(Error::InvalidEscape(Position::new(input, start))),
+    };
+
+    Ok((c, offset + 1))
+}
+
+fn decode_unicode(input: &[u8], start: usize) -> Result<(char, usize), Error> {
+    let invalid = || Error::InvalidUnicode(Position::new(input, start));
+    let high = decode_hex(input, start + 2)?;
+    let mut end = start + 6;
+
+    let code = match high {
+        0xd800..=0xdbff => {
+            if input.get(end..end + 2) != Some(b"\\u") {
+                return Err(invalid());
+            }
+
+            let low = decode_hex(input, end + 2)?;
+            if !(0xdc00..=0xdfff).contains(&low) {
+                return Err(invalid());
+            }
+            end += 6;
+
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        },
+        0xdc00..=0xdfff => return Err(invalid()),
+        code => code,
+    };
+
+    char::from_u32(code)
+        .map(|c| (c, end))
+        .ok_or_else(invalid)
+}
+
+fn decode_hex(input: &[u8], start: usize) -> Result<u32, Error> {
+    let mut code = 0;
+
+    for offset in start..start + 4 {
+        let digit = match input.get(offset) {
+            Some(c @ b'0'..=b'9') => c - b'0',
+            Some(c @ b'a'..=b'f') => c - b'a' + 10,
+            Some(c @ b'A'..=b'F') => c - b'A' + 10,
+            None => return Err(unexpected(input, offset)),
+            Some(_) => return Err(Error::InvalidEscape(Position::new(input, offset))),
+        };
+
+        code = code * 16 + digit as u32;
+    }
+
+    Ok(code)
+}
+
+/// Decodes the raw bytes of a [Event::String] or [Event::Key]. Borrows the
+/// input if there are no escapes. Positions in errors are relative to `raw`.
+pub fn unescape(raw: &[u8]) -> Result<Cow<'_, str>, Error> {
+    let Some(first) = raw.iter().position(|&c| c == b'\\') else {
+        return validate_utf8(raw, 0, raw.len()).map(Cow::Borrowed);
+    };
+
+    let mut string = String::from(validate_utf8(raw, 0, first)?);
+    let mut offset = first;
+
+    while offset < raw.len() {
+        let (c, end) = decode_escape(raw, offset)?;
+        string.push(c);
+
+        let next = raw[end..]
+            .iter()
+            .position(|&c| c == b'\\')
+            .map_or(raw.len(), |i| end + i);
+        string.push_str(validate_utf8(raw, end, next)?);
+        offset = next;
+    }
+
+    Ok(Cow::Owned(string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(input: &[u8]) -> Result<Vec<Event<'_>>, Error> {
+        Tokenizer::new(input).collect()
+    }
+
+    #[test]
+    fn tokenize() {
+        use Event::*;
+
+        let events = events(br#" {"a": [1, -2.5e3, "x\"y"], "b": {}, "c": [[]], "d": [true, false, null]} "#);
+        assert_eq!(events, Ok(vec![
+            StartObject,
+            Key(b"a"),
+            StartArray, Number(b"1"), Number(b"-2.5e3"), String(br#"x\"y"#), EndArray,
+            Key(b"b"),
+            StartObject, EndObject,
+            Key(b"c"),
+            StartArray, StartArray, EndArray, EndArray,
+            Key(b"d"),
+            StartArray, Bool(true), Bool(false), Null, EndArray,
+            EndObject,
+        ]));
+    }
+
+    #[test]
+    fn tokenize_errors() {
+        let mut tokenizer = Tokenizer::new(b"[1, {\"a\" 2}]");
+        assert_eq!(tokenizer.by_ref().take(4).filter(Result::is_ok).count(), 4);
+        assert_eq!(tokenizer.depth(), 2);
+        assert_eq!(tokenizer.next(), Some(Err(Error::UnexpectedChar('2', Position::new(b"[1, {\"a\" 2}]", 9)))));
+        assert_eq!(tokenizer.next(), None);
+
+        assert!(matches!(events(b"[1 2]"), Err(Error::UnexpectedChar('2', _))));
+        assert!(matches!(events(b"[1,]"), Err(Error::UnexpectedChar(']', _))));
+        assert!(matches!(events(b"{\"a\":1,}"), Err(Error::InvalidObjectKey(_))));
+        assert!(matches!(events(b"[1}"), Err(Error::UnbalancedBracket('}', _))));
+        assert!(matches!(events(b"[1]]"), Err(Error::UnbalancedBracket(']', _))));
+        assert!(matches!(events(b"[\"a\\q\"]"), Err(Error::InvalidEscape(_))));
+        assert!(matches!(events(b"[1] x"), Err(Error::LeftOverInput(..))));
+        assert!(events(b"[[[[0]]]]").is_ok());
+
+        let too_deep = Tokenizer::new(b"[[[[0]]]]").with_max_depth(3).collect::<Result<Vec<_>, _>>();
+        assert_eq!(too_deep, Err(Error::DepthLimitExceeded(Position::new(b"[[[[", 3))));
+    }
+
+    #[test]
+    fn unescape_strings() {
+        assert!(matches!(unescape(b"plain"), Ok(Cow::Borrowed("plain"))));
+        assert_eq!(unescape(br#"a\tb\\c\u00e9\ud83d\ude00d"#).as_deref(), Ok("a\tb\\cé😀d"));
+        assert_eq!(unescape(br#"\n"#).as_deref(), Ok("\n"));
+        assert!(matches!(unescape(br#"a\"#), Err(Error::UnexpectedEndOfInput(_))));
+        assert!(matches!(unescape(b"\xff"), Err(Error::InvalidUtf8(_))));
+    }
+}
@@ -0,0 +1,165 @@
+use super::{Digest, State, K, S};
+
+/// One step of the compression function across every lane. `f` is the
+/// round's boolean function and `g` the index of the message word.
+#[inline(always)]
+fn step<const LANES: usize, F>(words: &mut [[u32; LANES]; 4], chunk: &[[u32; LANES]; 16], i: usize, g: usize, f: F)
+where F: Fn(u32, u32, u32) -> u32
+{
+    let [a, b, c, d] = words;
+
+    for lane in 0..LANES {
+        let f = f(b[lane], c[lane], d[lane])
+            .wrapping_add(a[lane])
+            .wrapping_add(K[i])
+            .wrapping_add(chunk[g][lane]);
+
+        a[lane] = d[lane];
+        d[lane] = c[lane];
+        c[lane] = b[lane];
+        b[lane] = b[lane].wrapping_add(f.rotate_left(S[i]));
+    }
+}
+
+/// Compresses one block per lane. The state and message words are stored
+/// lane by lane, so each step is the same operation on `LANES` independent
+/// values, which the compiler can turn into SIMD instructions.
+fn transform_lanes<const LANES: usize>(state: &mut [[u32; LANES]; 4], chunk: &[[u32; LANES]; 16]) {
+    let mut words = *state;
+
+    // Spelling out every step makes the rotation amounts constants.
+    macro_rules! steps {
+        ($f:expr, $g:expr, [$($i:literal),*]) => {
+            $( step(&mut words, chunk, $i, $g($i), $f); )*
+        };
+    }
+
+    steps!(|b, c, d| (b & c) | (!b & d), |i: usize| i, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+    steps!(|b, c, d| (d & b) | (!d & c), |i: usize| (5 * i + 1) % 16, [16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31]);
+    steps!(|b, c, d| b ^ c ^ d, |i: usize| (3 * i + 5) % 16, [32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47]);
+    steps!(|b, c, d| c ^ (b | !d), |i: usize| (7 * i) % 16, [48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63]);
+
+    for (state, words) in state.iter_mut().zip(words) {
+        for lane in 0..LANES {
+            state[lane] = state[lane].wrapping_add(words[lane]);
+        }
+    }
+}
+
+/// Copies the part of `data`, which starts at `offset` in the message, that
+/// falls in the block starting at `block_start`.
+fn copy_into_block(block: &mut [u8; 64], block_start: usize, offset: usize, data: &[u8]) {
+    let start = offset.max(block_start);
+    let end = (offset + data.len()).min(block_start + 64);
+
+    if start < end {
+        block[start - block_start..end - block_start].copy_from_slice(&data[start - offset..end - offset]);
+    }
+}
+
+impl State {
+    /// Finishes `LANES` copies of this state, each with its own suffix, in
+    /// lockstep, so the hashes of many messages sharing a prefix can be
+    /// computed together from one midstate. Meant for 4, 8 or 16 lanes, and
+    /// without target specific features (such as AVX2) only 16 lanes are
+    /// reliably turned into SIMD instructions.
+    ///
+    /// Panics if the suffixes aren't all the same length.
+    pub fn digest_lanes<const LANES: usize>(&self, suffixes: &[&[u8]; LANES]) -> [Digest; LANES] {
+        let suffix_len = suffixes.first().map_or(0, |suffix| suffix.len());
+        assert!(suffixes.iter().all(|suffix| suffix.len() == suffix_len), "suffixes differ in length");
+
+        let buffered = (self.length % 64) as usize;
+        let message_len = buffered + suffix_len;
+        // Room for the `0x80` marker and the length, rounded up to a block.
+        let total = (message_len + 1 + 8).div_ceil(64) * 64;
+        let bit_length = self.length.wrapping_add(suffix_len as u64).wrapping_mul(8).to_le_bytes();
+
+        let mut state = self.state.map(|word| [word; LANES]);
+        let mut template = [0u8; 64];
+        let mut chunk = [[0u32; LANES]; 16];
+
+        for block_start in (0..total).step_by(64) {
+            // Everything but the suffix is the same in every lane.
+            template.fill(0);
+            copy_into_block(&mut template, block_start, 0, &self.buffer[..buffered]);
+            copy_into_block(&mut template, block_start, message_len, &[0x80]);
+            copy_into_block(&mut template, block_start, total - 8, &bit_length);
+
+            for (word, bytes) in chunk.iter_mut().zip(template.chunks_exact(4)) {
+                *word = [u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]); LANES];
+            }
+
+            // Only the words overlapping the suffix differ between lanes.
+            let start = buffered.clamp(block_start, block_start + 64) - block_start;
+            let end = message_len.clamp(block_start, block_start + 64) - block_start;
+            let words = start / 4..end.div_ceil(4);
+
+            for (lane, suffix) in suffixes.iter().enumerate() {
+                let mut block = template;
+                copy_into_block(&mut block, block_start, buffered, suffix);
+
+                for w in words.clone() {
+                    let bytes = &block[w * 4..w * 4 + 4];
+                    chunk[w][lane] = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                }
+            }
+
+            transform_lanes(&mut state, &chunk);
+        }
+
+        std::array::from_fn(|lane| {
+            let mut digest = [0u8; 16];
+            for (bytes, word) in digest.chunks_exact_mut(4).zip(&state) {
+                bytes.copy_from_slice(&word[lane].to_le_bytes());
+            }
+
+            Digest::from(digest)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::md5;
+
+    fn check_lanes<const LANES: usize>(prefix: &[u8], suffix_len: usize) {
+        let mut state = State::new();
+        state.update(prefix);
+
+        let suffixes: [Vec<u8>; LANES] = std::array::from_fn(|lane| {
+            (0..suffix_len).map(|i| (lane * 31 + i * 7) as u8).collect()
+        });
+        let expected = suffixes.clone().map(|suffix| md5([prefix, &suffix].concat()));
+        let suffixes = std::array::from_fn(|lane| suffixes[lane].as_slice());
+
+        assert_eq!(state.digest_lanes(&suffixes), expected, "prefix {}, suffix {}", prefix.len(), suffix_len);
+    }
+
+    #[test]
+    fn digest_lanes() {
+        // Cover suffixes ending in each block, and the padding spilling over
+        // into an extra block.
+        for prefix_len in [0, 1, 20, 55, 56, 63, 64, 100] {
+            let prefix: Vec<u8> = (0..prefix_len).map(|i| i as u8).collect();
+            for suffix_len in [0, 1, 7, 8, 9, 64, 130] {
+                check_lanes::<4>(&prefix, suffix_len);
+                check_lanes::<8>(&prefix, suffix_len);
+                check_lanes::<16>(&prefix, suffix_len);
+            }
+        }
+
+        let mut state = State::new();
+        state.update("abcdef");
+        let [a, b] = state.digest_lanes(&[b"609043", b"609042"]);
+        assert!(format!("{:x}", a).starts_with("00000"));
+        assert_eq!(b, md5("abcdef609042"));
+    }
+
+    #[test]
+    #[should_panic(expected = "suffixes differ in length")]
+    fn digest_lanes_length_mismatch() {
+        State::new().digest_lanes(&[b"1", b"10"]);
+    }
+}
@@ -1,3 +1,10 @@
+mod lanes;
+mod search;
+
+pub use search::{search, search_n};
+
+use super::hash::Hasher;
+
 const PADDING: [u8; 64] = [
     0x80, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
@@ -23,54 +30,7 @@ const S: [u32; 64] = [
     6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
 ];
 
-#[derive(Clone, Eq, PartialEq)]
-pub struct Digest([u8; 16]);
-
-impl std::convert::From<Digest> for [u8; 16] {
-    #[inline]
-    fn from(value: Digest) -> Self {
-        value.0
-    }
-}
-
-impl std::ops::Deref for Digest {
-    type Target = [u8; 16];
-
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl std::ops::DerefMut for Digest {
-    #[inline]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
-
-impl std::fmt::Debug for Digest {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::LowerHex::fmt(self, f)
-    }
-}
-
-macro_rules! implement_hex_fmt {
-    ($type:ident, $format:expr) => {
-        impl std::fmt::$type for Digest {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                for byte in &self.0 {
-                    write!(f, $format, byte)?;
-                }
-
-                Ok(())
-            }
-        }
-    };
-}
-
-implement_hex_fmt!(UpperHex, "{:02X}");
-implement_hex_fmt!(LowerHex, "{:02x}");
+pub type Digest = super::hash::Digest<16>;
 
 macro_rules! make_chunk {
     ($buffer:expr) => ({
@@ -208,7 +168,27 @@ impl State {
             j += 4;
         }
 
-        Digest(digest)
+        Digest::from(digest)
+    }
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for State {
+    type Digest = Digest;
+
+    fn update<T>(&mut self, data: T)
+    where T: AsRef<[u8]>
+    {
+        State::update(self, data)
+    }
+
+    fn digest(self) -> Digest {
+        State::digest(self)
     }
 }
 
@@ -250,6 +230,7 @@ mod tests{
             state.update(input);
             let digest = state.digest();
             assert_eq!(output, format!("{:x}", digest));
+            assert_eq!(<super::State as super::Hasher>::hash(input), output);
         }
     }
 }
@@ -0,0 +1,169 @@
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::{Digest, State};
+
+/// Number of nonces hashed together with [State::digest_lanes].
+const LANES: usize = 16;
+
+/// Number of consecutive nonces scanned by a worker at a time.
+#[cfg(feature = "parallel")]
+const CHUNK: u64 = 1 << 12;
+
+/// Writes the decimal digits of `n` to the start of `buffer`, returning
+/// how many there are.
+fn write_decimal(mut n: u64, buffer: &mut [u8; 20]) -> usize {
+    let mut len = 0;
+
+    loop {
+        buffer[len] = b'0' + (n % 10) as u8;
+        len += 1;
+        n /= 10;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    buffer[..len].reverse();
+    len
+}
+
+/// Hashes each nonce in `nonces` after the `prefix` midstate, in order,
+/// adding those whose digest satisfies `predicate` to `matches` until it
+/// holds `limit` of them.
+fn scan<P>(prefix: &State, nonces: Range<u64>, predicate: &P, limit: usize, matches: &mut Vec<(u64, Digest)>)
+where P: Fn(&Digest) -> bool
+{
+    let mut digits = [[0u8; 20]; LANES];
+    let mut n = nonces.start;
+
+    while n < nonces.end && matches.len() < limit {
+        let count = (nonces.end - n).min(LANES as u64) as usize;
+        let lens: [usize; LANES] = std::array::from_fn(|lane| write_decimal(n + lane.min(count - 1) as u64, &mut digits[lane]));
+
+        if count == LANES && lens.iter().all(|&len| len == lens[0]) {
+            let digests: [Digest; LANES] = prefix.digest_lanes(&std::array::from_fn(|lane| &digits[lane][..lens[0]]));
+            matches.extend((n..).zip(digests).filter(|(_, digest)| predicate(digest)));
+        } else {
+            // The nonces straddle a power of ten, or the end of the range, so
+            // hash them one at a time.
+            for lane in 0..count {
+                let mut state = prefix.clone();
+                state.update(&digits[lane][..lens[lane]]);
+                let digest = state.digest();
+
+                if predicate(&digest) {
+                    matches.push((n + lane as u64, digest));
+                }
+            }
+        }
+
+        matches.truncate(limit);
+        n += count as u64;
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn search_from<P>(prefix: &State, predicate: &P, start: u64, count: usize) -> Vec<(u64, Digest)>
+where P: Fn(&Digest) -> bool
+{
+    let mut matches = vec![];
+    scan(prefix, start..u64::MAX, predicate, count, &mut matches);
+
+    matches
+}
+
+/// Scans rounds of consecutive chunks in parallel. A round is only used once
+/// every chunk in it is done, so matches from later chunks can't displace
+/// lower nonces, and the result doesn't depend on scheduling.
+#[cfg(feature = "parallel")]
+fn search_from<P>(prefix: &State, predicate: &P, start: u64, count: usize) -> Vec<(u64, Digest)>
+where P: Fn(&Digest) -> bool + Sync
+{
+    let chunks_per_round = rayon::current_num_threads() as u64 * 4;
+    let mut matches = vec![];
+    let mut round_start = start;
+
+    while matches.len() < count && round_start < u64::MAX {
+        let remaining = count - matches.len();
+        let found: Vec<Vec<(u64, Digest)>> = (0..chunks_per_round)
+            .into_par_iter()
+            .map(|i| {
+                let chunk_start = round_start.saturating_add(i * CHUNK);
+                let mut found = vec![];
+                scan(prefix, chunk_start..chunk_start.saturating_add(CHUNK), predicate, remaining, &mut found);
+
+                found
+            })
+            .collect();
+
+        matches.extend(found.into_iter().flatten().take(remaining));
+        round_start = round_start.saturating_add(chunks_per_round * CHUNK);
+    }
+
+    matches
+}
+
+/// Finds the lowest nonce, counting up from `start`, for which the digest of
+/// `prefix` followed by the nonce in decimal satisfies `predicate`.
+///
+/// With the `parallel` feature the nonces are scanned by rayon workers, but
+/// the result is the same as scanning them in order.
+pub fn search<P>(prefix: &[u8], predicate: P, start: u64) -> Option<(u64, Digest)>
+where P: Fn(&Digest) -> bool + Sync
+{
+    search_n(prefix, predicate, start, 1).pop()
+}
+
+/// Like [search], but finds the lowest `count` matching nonces, in order.
+/// Returns fewer if the nonces run out first.
+pub fn search_n<P>(prefix: &[u8], predicate: P, start: u64, count: usize) -> Vec<(u64, Digest)>
+where P: Fn(&Digest) -> bool + Sync
+{
+    let mut state = State::new();
+    state.update(prefix);
+
+    search_from(&state, &predicate, start, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::md5;
+
+    #[test]
+    fn decimal_digits() {
+        let mut buffer = [0; 20];
+        for n in [0, 7, 10, 609043, u64::MAX] {
+            let len = write_decimal(n, &mut buffer);
+            assert_eq!(&buffer[..len], n.to_string().as_bytes());
+        }
+    }
+
+    #[test]
+    fn search_nonces() {
+        let five_zeroes = |digest: &Digest| digest[0] == 0 && digest[1] == 0 && digest[2] < 0x10;
+        assert_eq!(search(b"abcdef", five_zeroes, 1).map(|(nonce, _)| nonce), Some(609043));
+        assert_eq!(search(b"abcdef", five_zeroes, 609044).map(|(nonce, _)| nonce), Some(2102313));
+
+        // Nonces straddling powers of ten, which are hashed one at a time.
+        let ends_in_seven = |digest: &Digest| digest[15] & 0x0f == 7;
+        let expected: Vec<_> = (5..)
+            .map(|n| (n, md5(format!("xyz{}", n))))
+            .filter(|(_, digest)| ends_in_seven(digest))
+            .take(20)
+            .collect();
+        assert_eq!(search_n(b"xyz", ends_in_seven, 5, 20), expected);
+        assert_eq!(search_n(b"xyz", ends_in_seven, 5, 0), []);
+
+        let mut state = State::new();
+        state.update("xyz");
+        let mut matches = vec![];
+        scan(&state, u64::MAX - 40..u64::MAX, &|_: &Digest| true, 100, &mut matches);
+        assert_eq!(matches.len(), 40);
+        assert_eq!(matches[39], (u64::MAX - 1, md5(format!("xyz{}", u64::MAX - 1))));
+    }
+}
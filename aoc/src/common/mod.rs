@@ -1,10 +1,25 @@
 mod combinatorics;
-mod json;
-mod md5;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod json;
+pub mod md5;
 
 pub use combinatorics::*;
+pub use geometry::{Direction, Point, Point2, Point3, Point4};
+pub use graph::Graph;
+pub use grid::Grid;
+pub use hash::Hasher;
 pub use json::{
+    Descendants,
+    Event,
     Json,
+    JsonIndex,
+    Map,
+    Number,
+    Tokenizer,
+    Visit,
     error::Error,
 };
 
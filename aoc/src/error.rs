@@ -1,13 +1,16 @@
 use std::str::FromStr;
-use std::string::FromUtf8Error;
+use std::str::Utf8Error;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("IO error while loading input: {0:?}")]
     Io(#[from] std::io::Error),
 
+    #[error("unable to read input from {}", .0.display())]
+    ReadInput(std::path::PathBuf, #[source] std::io::Error),
+
     #[error("malformed UTF-8 string in input")]
-    Utf8(#[from] FromUtf8Error),
+    Utf8(#[from] Utf8Error),
 
     #[error(transparent)]
     Other(#[from] eyre::Report),
@@ -15,6 +18,9 @@ pub enum Error {
     #[error("no output for {0}/{1} part {}{}", <aoc_common::Part as FromStr>::from_str(.2).unwrap(), .3.as_ref().map(|v| format!(r#" (version "{v}""#)).unwrap_or_default())]
     NoOutput(usize, usize, String, Option<String>),
 
+    #[error("no output from generator for {0}/{1}")]
+    NoGeneratedInput(usize, usize),
+
     #[error("solution for {0}/{1} part {2}{} not registered", .3.clone().map(|v| format!(r#" (version "{v}""#)).unwrap_or_default())]
     NotRegistered(usize, usize, aoc_common::Part, Option<String>),
 
@@ -1,16 +1,108 @@
+use std::cell::RefCell;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
 
 use crate::error::Error;
 
+/// Environment variable naming the directory that puzzle inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+lazy_static! {
+    static ref INPUT_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+    static ref INPUT: Mutex<Option<Vec<u8>>> = Mutex::new(None);
+}
+
+thread_local! {
+    static THREAD_INPUT: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Set the directory that puzzle inputs are read from, unless
+/// [INPUT_DIR_VAR] is set.
+pub fn set_input_dir<P: Into<PathBuf>>(dir: P) {
+    *INPUT_DIR.lock().unwrap() = Some(dir.into());
+}
+
+/// Use `input` as the puzzle input of every solution, instead of reading
+/// inputs from the input directory.
+pub fn set_input(input: Vec<u8>) {
+    *INPUT.lock().unwrap() = Some(input);
+}
+
+/// Call `f` with `input` as the puzzle input of every solution run by `f` on
+/// the current thread, such as when running a solution on an example.
+pub fn with_input<F, T>(input: &[u8], f: F) -> T
+where F: FnOnce() -> T
+{
+    let previous = THREAD_INPUT.with(|i| i.replace(Some(input.to_vec())));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    THREAD_INPUT.with(|i| i.replace(previous));
+
+    result.unwrap_or_else(|e| panic::resume_unwind(e))
+}
+
+/// Root of the workspace: the closest directory above the current directory
+/// with a `Cargo.toml` declaring a `[workspace]`, falling back to the
+/// workspace this crate was built in, and then to the current directory.
+pub fn workspace_root() -> Result<PathBuf, Error> {
+    let current_dir = std::env::current_dir()?;
+    let find = |start: &Path| start
+        .ancestors()
+        .find(|dir| is_workspace_root(dir))
+        .map(Path::to_path_buf);
+
+    Ok(find(&current_dir)
+        .or_else(|| find(Path::new(env!("CARGO_MANIFEST_DIR"))))
+        .unwrap_or(current_dir))
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .is_ok_and(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]"))
+}
+
+/// Directory that puzzle inputs are read from, which is the first of:
+///
+/// 1. the value of [INPUT_DIR_VAR]
+/// 2. the directory given to [set_input_dir]
+/// 3. `data/input` in the [workspace_root]
+pub fn input_dir() -> Result<PathBuf, Error> {
+    if let Some(dir) = std::env::var_os(INPUT_DIR_VAR).filter(|dir| !dir.is_empty()) {
+        return Ok(dir.into());
+    }
+    if let Some(dir) = INPUT_DIR.lock().unwrap().clone() {
+        return Ok(dir);
+    }
+
+    Ok(workspace_root()?.join("data/input"))
+}
+
+/// Path of the puzzle input for `year` and `day`, within the [input_dir].
+pub fn input_path(year: usize, day: usize) -> Result<PathBuf, Error> {
+    Ok(input_dir()?.join(format!("y{year}/day{day:02}.txt")))
+}
+
+/// Puzzle input for `year` and `day`, which is the input given to
+/// [with_input] or [set_input] if there is one, or the file at [input_path].
 pub fn input_bytes(year: usize, day: usize) -> Result<Vec<u8>, Error> {
-    Ok(std::fs::read(format!(
-        "{}/data/input/y{year}/day{day:02}.txt",
-        std::env::current_dir().unwrap().display(),
-    ))?)
+    if let Some(input) = THREAD_INPUT.with(|i| i.borrow().clone()) {
+        return Ok(input);
+    }
+    if let Some(input) = INPUT.lock().unwrap().as_ref() {
+        return Ok(input.clone());
+    }
+
+    let path = input_path(year, day)?;
+    fs::read(&path).map_err(|e| Error::ReadInput(path, e))
 }
 
 pub fn input_string(year: usize, day: usize) -> Result<String, Error> {
-    Ok(String::from_utf8(input_bytes(year, day)?)?)
+    String::from_utf8(input_bytes(year, day)?)
+        .map_err(|e| e.utf8_error().into())
 }
 
 pub fn parse_input<T>(input: &str, sep: Option<&str>) -> Result<Vec<T>, Error>
@@ -41,3 +133,224 @@ pub fn parse_input_bytes(input: &[u8], sep: Option<u8>) -> Result<Vec<&[u8]>, Er
         .collect()
     )
 }
+
+/// Parse the whole of `input` with [FromStr].
+///
+/// Useful for implementing [FromInput] for types that already implement [FromStr].
+pub fn parse_str<T>(input: &[u8]) -> Result<T, Error>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<eyre::Report>,
+{
+    std::str::from_utf8(input)?
+        .parse()
+        .map_err(Error::from_error)
+}
+
+/// Conversion from the raw bytes of a puzzle input into the argument of a
+/// solution or generator.
+///
+/// `separator` is the value of the `separator` key given to [crate::aoc] or
+/// [crate::aoc_generator], if any.
+///
+/// When an argument is a reference `&T` or `&mut T`, `T` is built with this
+/// trait and then borrowed, with slices `[T]` being built as `Vec<T>`. The
+/// exceptions are `&str` and `&[u8]`, which borrow the input directly.
+pub trait FromInput<'a>: Sized {
+    fn from_input(input: &'a [u8], separator: Option<&str>) -> Result<Self, Error>;
+}
+
+impl<'a> FromInput<'a> for &'a [u8] {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, Error> {
+        Ok(input)
+    }
+}
+
+impl<'a> FromInput<'a> for &'a str {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, Error> {
+        Ok(std::str::from_utf8(input)?)
+    }
+}
+
+/// Lines of the input, or the input split on the first byte of `separator`.
+impl<'a> FromInput<'a> for Vec<&'a [u8]> {
+    fn from_input(input: &'a [u8], separator: Option<&str>) -> Result<Self, Error> {
+        parse_input_bytes(input, separator.and_then(|sep| sep.bytes().next()))
+    }
+}
+
+/// Lines of the input, or the trimmed input split on `separator`.
+impl<'a> FromInput<'a> for Vec<&'a str> {
+    fn from_input(input: &'a [u8], separator: Option<&str>) -> Result<Self, Error> {
+        let input = <&str>::from_input(input, None)?;
+
+        Ok(match separator {
+            Some(sep) => input.trim().split(sep).collect(),
+            None => input.lines().collect(),
+        })
+    }
+}
+
+/// Grid of bytes, with one row per line.
+impl<'a> FromInput<'a> for Vec<Vec<u8>> {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, Error> {
+        Ok(<&str>::from_input(input, None)?
+            .lines()
+            .map(|line| line.as_bytes().to_vec())
+            .collect()
+        )
+    }
+}
+
+/// Grid of characters, with one row per line.
+impl<'a> FromInput<'a> for Vec<Vec<char>> {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, Error> {
+        Ok(<&str>::from_input(input, None)?
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect()
+        )
+    }
+}
+
+macro_rules! impl_from_input_parse {
+    (__impl $ty:ty) => {
+        /// Each line of the input, or each part of the trimmed input split on
+        /// `separator`, parsed with [FromStr].
+        impl<'a> FromInput<'a> for Vec<$ty> {
+            fn from_input(input: &'a [u8], separator: Option<&str>) -> Result<Self, Error> {
+                parse_input(<&str>::from_input(input, None)?, separator)
+            }
+        }
+    };
+    ($ty:ty) => {
+        impl_from_input_parse!(__impl $ty);
+    };
+    ($ty:ty, $($tys:ty),+) => {
+        impl_from_input_parse!(__impl $ty);
+        impl_from_input_parse!($($tys),+);
+    };
+}
+
+impl_from_input_parse!(isize, i8, i16, i32, i64, i128);
+impl_from_input_parse!(usize, u8, u16, u32, u64, u128);
+impl_from_input_parse!(f32, f64, String);
+
+/// Each line of the input, or each part of the trimmed input split on
+/// `separator`, parsed with [FromStr] into a `T`.
+///
+/// This works for any `T: FromStr`, including types defined by solutions.
+/// `Vec<T>` itself is only built this way for numbers and [String], as a
+/// blanket impl would overlap with `Vec<&str>` and `Vec<&[u8]>`.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Lines<T>(pub Vec<T>);
+
+impl<T> std::ops::Deref for Lines<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Lines<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Lines<T>> for Vec<T> {
+    fn from(lines: Lines<T>) -> Self {
+        lines.0
+    }
+}
+
+impl<T> IntoIterator for Lines<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> FromInput<'a> for Lines<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<eyre::Report>,
+{
+    fn from_input(input: &'a [u8], separator: Option<&str>) -> Result<Self, Error> {
+        parse_input(<&str>::from_input(input, None)?, separator).map(Lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_workspace_root() {
+        let root = workspace_root().unwrap();
+        assert!(is_workspace_root(&root));
+        assert!(root.join("aoc/src/input.rs").exists());
+        assert!(!is_workspace_root(&root.join("aoc")));
+    }
+
+    #[test]
+    fn thread_input() {
+        assert_eq!(with_input(b"outer", || {
+            let inner = with_input(b"inner", || input_bytes(2015, 1).unwrap());
+            (inner, input_bytes(2015, 1).unwrap())
+        }), (b"inner".to_vec(), b"outer".to_vec()));
+
+        let panicked = std::panic::catch_unwind(|| with_input(b"input", || panic!()));
+        assert!(panicked.is_err());
+        assert!(THREAD_INPUT.with(|i| i.borrow().is_none()));
+    }
+
+    #[test]
+    fn from_input() {
+        let input = b"12\n-3\n45\n";
+        assert_eq!(<&[u8]>::from_input(input, None).unwrap(), input);
+        assert_eq!(<&str>::from_input(input, None).unwrap(), "12\n-3\n45\n");
+        assert_eq!(Vec::<&str>::from_input(input, None).unwrap(), vec!["12", "-3", "45"]);
+        assert_eq!(Vec::<&[u8]>::from_input(input, None).unwrap(), vec![&b"12"[..], b"-3", b"45"]);
+        assert_eq!(Vec::<i64>::from_input(input, None).unwrap(), vec![12, -3, 45]);
+        assert!(Vec::<u64>::from_input(input, None).is_err());
+
+        let input = b"1,2,3\n";
+        assert_eq!(Vec::<&str>::from_input(input, Some(",")).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(Vec::<u8>::from_input(input, Some(",")).unwrap(), vec![1, 2, 3]);
+
+        let input = b"#.\n.#\n";
+        assert_eq!(Vec::<Vec<u8>>::from_input(input, None).unwrap(), vec![b"#.".to_vec(), b".#".to_vec()]);
+        assert_eq!(Vec::<Vec<char>>::from_input(input, None).unwrap(), vec![vec!['#', '.'], vec!['.', '#']]);
+
+        assert!(<&str>::from_input(&[0xFF, 0xFE], None).is_err());
+    }
+
+    #[test]
+    fn lines_from_input() {
+        #[derive(Debug, PartialEq)]
+        struct Dimensions(u32, u32);
+
+        impl FromStr for Dimensions {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (w, h) = s.split_once('x').unwrap_or((s, ""));
+                Ok(Self(w.parse()?, h.parse()?))
+            }
+        }
+
+        let lines = Lines::<Dimensions>::from_input(b"2x3\n10x4\n", None).unwrap();
+        assert_eq!(*lines, [Dimensions(2, 3), Dimensions(10, 4)]);
+        assert_eq!(lines.into_iter().map(|d| d.0 * d.1).sum::<u32>(), 46);
+
+        let lines = Lines::<Dimensions>::from_input(b"1x1;2x2\n", Some(";")).unwrap();
+        assert_eq!(Vec::from(lines), [Dimensions(1, 1), Dimensions(2, 2)]);
+
+        assert!(Lines::<Dimensions>::from_input(b"2x3\n10\n", None).is_err());
+        assert!(Lines::<Dimensions>::from_input(&[0xFF], None).is_err());
+    }
+}
@@ -8,4 +8,7 @@ mod runners;
 mod solutions;
 
 pub use register::register_runners;
-pub use runners::{get_runner, register_runner};
+pub use runners::{
+    get_examples, get_runner, get_runners, register_example, register_runner,
+    Example, RegisteredRunner, Timings,
+};
@@ -2,17 +2,41 @@ use std::{
     collections::BTreeMap,
     fmt::Display,
     sync::{Mutex, Arc},
+    time::Duration,
 };
 
 use lazy_static::lazy_static;
 
 use crate::error::Error;
 
-type Runner = dyn Fn() -> eyre::Result<String> + Send + Sync + 'static;
+type Runner = dyn Fn(&mut Timings) -> eyre::Result<String> + Send + Sync + 'static;
+
+/// Year, day, part, and version of a solution.
+type SolutionKey = (usize, usize, aoc_common::Part, Option<String>);
+
+/// Time spent in each phase of a single run of a solution.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timings {
+    /// Reading the puzzle input.
+    pub load: Duration,
+    /// Converting the puzzle input into the solution's argument, either
+    /// directly or with a generator.
+    pub generate: Duration,
+    /// Running the solution itself.
+    pub solve: Duration,
+}
+
+impl Timings {
+    pub fn total(&self) -> Duration {
+        self.load + self.generate + self.solve
+    }
+}
 
 lazy_static! {
     pub(crate) static ref RUNNERS: Mutex<BTreeMap<(usize, usize, aoc_common::Part), Vec<(Option<String>, Arc<Runner>)>>> =
         Mutex::new(BTreeMap::new());
+    static ref EXAMPLES: Mutex<BTreeMap<SolutionKey, Vec<Example>>> =
+        Mutex::new(BTreeMap::new());
 }
 
 pub fn get_runner(year: usize, day: usize, part: aoc_common::Part, version: Option<String>) -> Result<Arc<Runner>, Error> {
@@ -35,15 +59,88 @@ pub fn get_runner(year: usize, day: usize, part: aoc_common::Part, version: Opti
     Err(Error::NotRegistered(year, day, part, version))
 }
 
+/// A registered solution, as returned by [get_runners].
+#[derive(Clone)]
+pub struct RegisteredRunner {
+    pub year: usize,
+    pub day: usize,
+    pub part: aoc_common::Part,
+    pub version: Option<String>,
+    pub runner: Arc<Runner>,
+}
+
+/// Get every registered runner for `year`, or for every year if `year` is `None`.
+///
+/// Runners are ordered by year, day, and part, with the default version of a
+/// solution ahead of any alternate versions.
+pub fn get_runners(year: Option<usize>) -> Vec<RegisteredRunner> {
+    let map = RUNNERS.lock().unwrap();
+    let mut runners = Vec::new();
+
+    for (&(y, day, part), alts) in map.iter() {
+        if year.is_some_and(|year| year != y) {
+            continue;
+        }
+
+        let mut alts = alts.clone();
+        alts.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (version, runner) in alts {
+            runners.push(RegisteredRunner {
+                year: y,
+                day,
+                part,
+                version,
+                runner,
+            });
+        }
+    }
+
+    runners
+}
+
+/// Example input for a solution, with the answer it should give.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Example {
+    /// `#n` for the `n`th example declared on the solution, or the path of
+    /// the example's file.
+    pub name: String,
+    pub input: &'static str,
+    pub expected: String,
+}
+
+/// Get the examples registered for a solution, in the order they were registered.
+pub fn get_examples(year: usize, day: usize, part: aoc_common::Part, version: Option<String>) -> Vec<Example> {
+    EXAMPLES
+        .lock()
+        .unwrap()
+        .get(&(year, day, part, version))
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn register_example(year: usize, day: usize, part: &str, version: Option<String>, name: &str, input: &'static str, expected: &str) -> eyre::Result<()> {
+    let mut map = EXAMPLES.lock().unwrap();
+    map.entry((year, day, part.parse()?, version))
+        .or_default()
+        .push(Example {
+            name: name.to_string(),
+            input,
+            expected: expected.to_string(),
+        });
+
+    Ok(())
+}
+
 pub fn register_runner<F, T>(year: usize, day: usize, part: &str, version: Option<String>, func: F) -> eyre::Result<()>
 where
-    F: Fn() -> eyre::Result<T> + Send + Sync + 'static,
+    F: Fn(&mut Timings) -> eyre::Result<T> + Send + Sync + 'static,
     T: Display,
 {
     let mut map = RUNNERS.lock().unwrap();
     map.entry((year, day, part.parse()?))
         .or_insert_with(Vec::new)
-        .push((version, Arc::new(move || func().map(|r| r.to_string()))));
+        .push((version, Arc::new(move |timings: &mut Timings| func(timings).map(|r| r.to_string()))));
 
     Ok(())
 }
@@ -19,7 +19,12 @@ fn count_leading_zeroes(digest: &Digest) -> usize {
     count
 }
 
-fn day04(input: &str, num_leading_zeroes: usize) -> u64 {
+fn day04(input: &str, num_leading_zeroes: usize) -> Option<u64> {
+    md5::search(input.as_bytes(), |digest| count_leading_zeroes(digest) >= num_leading_zeroes, 1)
+        .map(|(nonce, _)| nonce)
+}
+
+fn day04_scalar(input: &str, num_leading_zeroes: usize) -> u64 {
     let mut original = State::new();
     original.update(input);
     let mut n = 1;
@@ -40,15 +45,25 @@ fn day04(input: &str, num_leading_zeroes: usize) -> u64 {
 }
 
 #[crate::aoc(year = 2015, day = 4, part = "A")]
-fn day04a(input: &str) -> u64 {
+fn day04a(input: &str) -> Option<u64> {
     day04(input.trim_end(), 5)
 }
 
+#[crate::aoc(year = 2015, day = 4, part = "A", version = "scalar")]
+fn day04a_scalar(input: &str) -> u64 {
+    day04_scalar(input.trim_end(), 5)
+}
+
 #[crate::aoc(year = 2015, day = 4, part = "B")]
-fn day04b(input: &str) -> u64 {
+fn day04b(input: &str) -> Option<u64> {
     day04(input.trim_end(), 6)
 }
 
+#[crate::aoc(year = 2015, day = 4, part = "B", version = "scalar")]
+fn day04b_scalar(input: &str) -> u64 {
+    day04_scalar(input.trim_end(), 6)
+}
+
 #[cfg(test)]
 mod tests_y2015_day04 {
     use super::*;
@@ -56,7 +71,8 @@ mod tests_y2015_day04 {
     #[test]
     #[allow(non_snake_case)]
     fn partA() {
-        assert_eq!(day04a("abcdef"), 609043);
-        assert_eq!(day04a("pqrstuv"), 1048970);
+        assert_eq!(day04a("abcdef"), Some(609043));
+        assert_eq!(day04a("pqrstuv"), Some(1048970));
+        assert_eq!(day04a_scalar("abcdef"), 609043);
     }
 }
@@ -6,6 +6,8 @@ use nom::{
     IResult,
 };
 
+use crate::common::Grid;
+
 #[derive(Debug)]
 enum Error {
     InstructionKindParse,
@@ -87,112 +89,43 @@ impl Instruction {
     }
 }
 
-trait Grid {
-    fn interpret(&mut self, instruction: Instruction);
-
-    fn total_brightness(self) -> usize;
-}
-
-struct BoolGrid {
-    data: Vec<bool>,
-}
-
-impl BoolGrid {
-    fn new() -> Self {
-        Self {
-            data: vec![false; 1000 * 1000],
-        }
-    }
-}
-
-impl Grid for BoolGrid {
-    fn interpret(&mut self, instruction: Instruction) {
-        let Instruction { kind, x1, y1, x2, y2 } = instruction;
-
-        for y in y1..=y2 {
-            for x in x1..=x2 {
-                let i = y * 1000 + x;
-                if let Some(b) = self.data.get_mut(i) {
-                    match kind {
-                        InstructionKind::TurnOn => *b = true,
-                        InstructionKind::TurnOff => *b = false,
-                        InstructionKind::Toggle => *b = !*b,
-                    }
-                }
-            }
-        }
-    }
-
-    fn total_brightness(self) -> usize {
-        self.data
-            .into_iter()
-            .filter(|&b| b)
-            .count()
-    }
-}
-
-struct IntGrid {
-    data: Vec<usize>,
-}
-
-impl IntGrid {
-    fn new() -> Self {
-        Self {
-            data: vec![0; 1000 * 1000],
-        }
-    }
-}
-
-impl Grid for IntGrid {
-    fn interpret(&mut self, instruction: Instruction) {
-        let Instruction { kind, x1, y1, x2, y2 } = instruction;
-
-        for y in y1..=y2 {
-            for x in x1..=x2 {
-                let i = y * 1000 + x;
-                if let Some(n) = self.data.get_mut(i) {
-                    match kind {
-                        InstructionKind::TurnOn => *n = n.saturating_add(1),
-                        InstructionKind::TurnOff => *n = n.saturating_sub(1),
-                        InstructionKind::Toggle => *n = n.saturating_add(2),
-                    }
-                }
-            }
-        }
-    }
-
-    fn total_brightness(self) -> usize {
-        self.data
-            .into_iter()
-            .sum()
-    }
-}
-
-fn day06<F, G>(lines: &[&str], make_grid: F) -> usize
+fn day06<T, F>(lines: &[&str], initial: T, apply: F) -> Grid<T>
 where
-    F: Fn() -> G,
-    G: Grid,
+    T: Clone,
+    F: Fn(&InstructionKind, &mut T),
 {
-    let mut grid = make_grid();
+    let mut grid = Grid::new(1000, 1000, initial);
     for (i, &line) in lines.iter().enumerate() {
-        if let Ok(instruction) = Instruction::new(line) {
-            grid.interpret(instruction);
+        if let Ok(Instruction { kind, x1, y1, x2, y2 }) = Instruction::new(line) {
+            grid.region_mut_clamped(x1..=x2, y1..=y2)
+                .for_each(|light| apply(&kind, light));
         } else {
             eprintln!("error on line {i}");
         }
     }
 
-    grid.total_brightness()
+    grid
 }
 
 #[crate::aoc(year = 2015, day = 6, part = "A")]
 fn day06a(lines: &[&str]) -> usize {
-    day06(lines, BoolGrid::new)
+    day06(lines, false, |kind, on| match kind {
+        InstructionKind::TurnOn => *on = true,
+        InstructionKind::TurnOff => *on = false,
+        InstructionKind::Toggle => *on = !*on,
+    })
+    .count(|&on| on)
 }
 
 #[crate::aoc(year = 2015, day = 6, part = "B")]
 fn day06b(lines: &[&str]) -> usize {
-    day06(lines, IntGrid::new)
+    day06(lines, 0usize, |kind, n| match kind {
+        InstructionKind::TurnOn => *n = n.saturating_add(1),
+        InstructionKind::TurnOff => *n = n.saturating_sub(1),
+        InstructionKind::Toggle => *n = n.saturating_add(2),
+    })
+    .iter()
+    .sum()
 }
 
 #[cfg(test)]
@@ -208,6 +141,12 @@ mod tests_y2015_day06 {
             "turn off 499,499 through 500,500",
         ]);
         assert_eq!(res, 1000 * 1000 - 1000 - 4);
+
+        let res = day06a(&[
+            "turn on 995,998 through 1005,1005",
+            "toggle 10,10 through 5,5",
+        ]);
+        assert_eq!(res, 5 * 2);
     }
 
     #[test]
@@ -1,73 +1,65 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+
+use crate::common::Graph;
 
 #[derive(Debug)]
-enum Error<'a> {
-    WireNumberParse(&'a str),
-    UnknownOperator(&'a str),
+enum Error {
+    WireNumberParse(String),
+    UnknownOperator(String),
     InvalidNumberOfArguments(usize),
-    InstructionParse(&'a str),
+    InstructionParse(String),
+    CyclicWires(Vec<String>),
 }
 
-impl<'a> std::fmt::Display for Error<'a> {
+impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
+        match self {
             Self::WireNumberParse(s) => write!(f, "unable to parse u16 from `{}`", s)?,
             Self::UnknownOperator(s) => write!(f, "unknown operator: `{}`", s)?,
             Self::InvalidNumberOfArguments(n) => write!(f, "invalid number of arguments: {}", n)?,
             Self::InstructionParse(s) => write!(f, "unable to parse Instruction from `{}`", s)?,
+            Self::CyclicWires(wires) => write!(f, "wires depend on each other: {}", wires.join(" -> "))?,
         }
 
         Ok(())
     }
 }
 
-impl<'a> std::error::Error for Error<'a> {}
+impl std::error::Error for Error {}
 
-#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
-enum Wire<'a> {
-    Name(&'a str),
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Wire {
+    Name(String),
     Number(u16),
 }
 
-impl<'a> Wire<'a> {
-    fn parse(s: &'a str) -> Result<Self, Error<'a>> {
+impl Wire {
+    fn parse(s: &str) -> Result<Self, Error> {
         match s.chars().nth(0) {
-            None => Err(Error::WireNumberParse(s)),
+            None => Err(Error::WireNumberParse(s.to_string())),
             Some(c) if c.is_ascii_digit() => {
                 match u16::from_str_radix(s, 10) {
                     Ok(num) => Ok(Self::Number(num)),
-                    Err(_) => Err(Error::WireNumberParse(s)),
+                    Err(_) => Err(Error::WireNumberParse(s.to_string())),
                 }
             },
-            Some(_) => Ok(Self::Name(s)),
+            Some(_) => Ok(Self::Name(s.to_string())),
         }
     }
 }
 
-impl<'a> From<&'a str> for Wire<'a> {
-    fn from(value: &'a str) -> Self {
-        Self::Name(value)
-    }
-}
-
-impl<'a> From<&'a Wire<'a>> for Wire<'a> {
-    fn from(value: &'a Wire<'a>) -> Self {
-        *value
-    }
-}
-
-#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
-enum Gate<'a> {
-    Set(Wire<'a>),
-    Not(Wire<'a>),
-    And(Wire<'a>, Wire<'a>),
-    Or(Wire<'a>, Wire<'a>),
-    LShift(Wire<'a>, Wire<'a>),
-    RShift(Wire<'a>, Wire<'a>),
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Gate {
+    Set(Wire),
+    Not(Wire),
+    And(Wire, Wire),
+    Or(Wire, Wire),
+    LShift(Wire, Wire),
+    RShift(Wire, Wire),
 }
 
-impl<'a> Gate<'a> {
-    fn parse(parts: &[&'a str]) -> Result<Self, Error<'a>> {
+impl Gate {
+    fn parse(parts: &[&str]) -> Result<Self, Error> {
         match parts.len() {
             1 => Ok(Self::Set(Wire::parse(parts[0])?)),
             2 => Ok(Self::Not(Wire::parse(parts[1])?)),
@@ -79,22 +71,32 @@ impl<'a> Gate<'a> {
                     "OR" => Ok(Self::Or(left, right)),
                     "LSHIFT" => Ok(Self::LShift(left, right)),
                     "RSHIFT" => Ok(Self::RShift(left, right)),
-                    _ => Err(Error::UnknownOperator(parts[1])),
+                    _ => Err(Error::UnknownOperator(parts[1].to_string())),
                 }
             },
             n => Err(Error::InvalidNumberOfArguments(n)),
         }
     }
+
+    fn inputs(&self) -> [Option<&Wire>; 2] {
+        match self {
+            Self::Set(wire) | Self::Not(wire) => [Some(wire), None],
+            Self::And(left, right)
+            | Self::Or(left, right)
+            | Self::LShift(left, right)
+            | Self::RShift(left, right) => [Some(left), Some(right)],
+        }
+    }
 }
 
-#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
-struct Instruction<'a> {
-    input: Gate<'a>,
-    output: &'a str,
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct Instruction {
+    input: Gate,
+    output: String,
 }
 
-impl<'a> Instruction<'a> {
-    fn new(line: &'a str) -> Result<Self, Error<'a>> {
+impl Instruction {
+    fn new(line: &str) -> Result<Self, Error> {
         let parts: Vec<&str> = line.split(" ").collect();
         if let Some(index) = parts.iter().position(|&p| p == "->") {
             let (left, right) = parts.split_at(index);
@@ -102,85 +104,26 @@ impl<'a> Instruction<'a> {
             Gate::parse(left).map(|input| {
                 Self {
                     input,
-                    output: right[1],
+                    output: right[1].to_string(),
                 }
             })
         } else {
-            Err(Error::InstructionParse(line))
+            Err(Error::InstructionParse(line.to_string()))
         }
     }
 }
 
-struct Circuit<'a> {
-    instructions: Vec<Instruction<'a>>,
-    values: HashMap<&'a str, u16>,
+struct Circuit {
+    instructions: Vec<Instruction>,
+    values: HashMap<String, u16>,
 }
 
-impl<'a> Circuit<'a> {
-    fn new(lines: &'a [&str]) -> Result<Self, Error<'a>> {
-        let res: Result<Vec<_>, _> = lines
-            .into_iter()
-            .map(|&line| Instruction::new(line))
-            .collect();
-
-        res.map(|instructions| {
-            let mut instructions = instructions;
-            let mut outstructions = vec![]; // lol
-            let mut end = instructions.len();
-            let mut known: HashSet<&str> = HashSet::new();
-
-            while !instructions.is_empty() {
-                'inner: for i in 0..end {
-                    let all_inputs_known = match &instructions[i].input {
-                        Gate::Set(wire) => match wire {
-                            Wire::Number(_) => true,
-                            Wire::Name(name) => known.contains(name),
-                        },
-                        Gate::Not(wire) => match wire {
-                            Wire::Number(_) => true,
-                            Wire::Name(name) => known.contains(name),
-                        },
-                        Gate::And(left, right) => match (left, right) {
-                            (Wire::Number(_), Wire::Number(_)) => true,
-                            (Wire::Name(n1), Wire::Name(n2)) => known.contains(n1) && known.contains(n2),
-                            (Wire::Name(name), _) => known.contains(name),
-                            (_, Wire::Name(name)) => known.contains(name),
-                        },
-                        Gate::Or(left, right) => match (left, right) {
-                            (Wire::Number(_), Wire::Number(_)) => true,
-                            (Wire::Name(n1), Wire::Name(n2)) => known.contains(n1) && known.contains(n2),
-                            (Wire::Name(name), _) => known.contains(name),
-                            (_, Wire::Name(name)) => known.contains(name),
-                        },
-                        Gate::LShift(left, right) => match (left, right) {
-                            (Wire::Number(_), Wire::Number(_)) => true,
-                            (Wire::Name(n1), Wire::Name(n2)) => known.contains(n1) && known.contains(n2),
-                            (Wire::Name(name), _) => known.contains(name),
-                            (_, Wire::Name(name)) => known.contains(name),
-                        },
-                        Gate::RShift(left, right) => match (left, right) {
-                            (Wire::Number(_), Wire::Number(_)) => true,
-                            (Wire::Name(n1), Wire::Name(n2)) => known.contains(n1) && known.contains(n2),
-                            (Wire::Name(name), _) => known.contains(name),
-                            (_, Wire::Name(name)) => known.contains(name),
-                        },
-                    };
-
-                    if all_inputs_known {
-                        let inst = instructions.swap_remove(i);
-                        known.insert(inst.output);
-                        outstructions.push(inst);
-                        end -= 1;
-                        break 'inner;
-                    }
-                }
-            }
-
-            Self {
-                instructions: outstructions,
-                values: HashMap::new(),
-            }
-        })
+impl Circuit {
+    fn new(instructions: Vec<Instruction>) -> Self {
+        Self {
+            instructions,
+            values: HashMap::new(),
+        }
     }
 
     fn run(&mut self) -> Result<(), ()> {
@@ -212,70 +155,121 @@ impl<'a> Circuit<'a> {
                 },
             };
 
-            self.values.insert(instruction.output, value);
+            self.values.insert(instruction.output.clone(), value);
         }
 
         Ok(())
     }
 
-    fn override_wire(&mut self, wire: &'a str, b: u16) {
+    fn override_wire(&mut self, wire: &str, b: u16) {
         let inst = Instruction {
             input: Gate::Set(Wire::Number(b)),
-            output: wire,
+            output: wire.to_string(),
         };
         if let Some(index) = self.instructions.iter().position(|i| i.output == wire) {
             self.instructions[index] = inst;
         }
     }
 
-    fn get<W>(&self, wire: W) -> Option<u16>
-    where W: Into<Wire<'a>>
-    {
-        match wire.into() {
-            Wire::Number(n) => Some(n),
-            Wire::Name(name) => match self.values.get(name) {
-                Some(&value) => Some(value),
-                None => None,
-            }
+    fn get(&self, wire: &Wire) -> Option<u16> {
+        match wire {
+            Wire::Number(n) => Some(*n),
+            Wire::Name(name) => self.value(name),
         }
     }
+
+    fn value(&self, name: &str) -> Option<u16> {
+        self.values.get(name).copied()
+    }
 }
 
-fn day07(lines: &[&str], times: usize) -> Option<u16> {
-    match Circuit::new(lines) {
-        Ok(mut circuit) => {
-            let mut a: Option<u16> = None;
+/// Parses the instructions, ordered so that every wire is set before it is
+/// read.
+#[crate::aoc_generator(year = 2015, day = 7)]
+fn day07_input(lines: &[&str]) -> Result<Vec<Instruction>, Error> {
+    let instructions = lines
+        .iter()
+        .map(|&line| Instruction::new(line))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut wires: Graph<&str, ()> = Graph::new();
+    for instruction in instructions.iter() {
+        wires.intern(&instruction.output);
+
+        for wire in instruction.input.inputs().into_iter().flatten() {
+            if let Wire::Name(name) = wire {
+                wires.add_edge(name, &instruction.output, ());
+            }
+        }
+    }
+
+    let order = wires.topological_sort()
+        .map_err(|cycle| Error::CyclicWires(cycle.nodes.into_iter().map(|id| wires.key(id).to_string()).collect()))?;
+    let mut rank = vec![0; order.len()];
+    order.into_iter().enumerate().for_each(|(i, id)| rank[id] = i);
+    let ranks: Vec<usize> = instructions
+        .iter()
+        .map(|instruction| rank[wires.id(&instruction.output.as_str()).unwrap()])
+        .collect();
 
-            for _ in 0..times {
-                a = match circuit.run() {
-                    Ok(_) => circuit.get("a"),
-                    Err(_) => return None,
-                };
+    let mut ranked: Vec<(usize, Instruction)> = ranks.into_iter().zip(instructions).collect();
+    ranked.sort_by_key(|&(rank, _)| rank);
 
-                circuit.override_wire("b", a.unwrap());
-            }
+    Ok(ranked.into_iter().map(|(_, instruction)| instruction).collect())
+}
 
-            a
-        },
-        Err(e) => {
-            eprintln!("{e}");
-            None
-        },
+fn day07(instructions: &[Instruction], times: usize) -> Option<u16> {
+    let mut circuit = Circuit::new(instructions.to_vec());
+    let mut a: Option<u16> = None;
+
+    for _ in 0..times {
+        a = match circuit.run() {
+            Ok(_) => circuit.value("a"),
+            Err(_) => return None,
+        };
+
+        circuit.override_wire("b", a?);
     }
+
+    a
 }
 
-#[crate::aoc(year = 2015, day = 7, part = "A")]
-fn day07a(lines: &[&str]) -> Option<u16> {
-    day07(lines, 1)
+#[crate::aoc(year = 2015, day = 7, part = "A", generator)]
+fn day07a(instructions: &[Instruction]) -> Option<u16> {
+    day07(instructions, 1)
 }
 
-#[crate::aoc(year = 2015, day = 7, part = "B")]
-fn day07b(lines: &[&str]) -> Option<u16> {
-    day07(lines, 2)
+#[crate::aoc(year = 2015, day = 7, part = "B", generator)]
+fn day07b(instructions: &[Instruction]) -> Option<u16> {
+    day07(instructions, 2)
 }
 
 #[cfg(test)]
 mod tests_y2015_day07 {
+    use super::*;
+
+    #[test]
+    fn circuit() {
+        let lines = &[
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i",
+            "123 -> x",
+            "456 -> y",
+        ];
+        let mut circuit = Circuit::new(day07_input(lines).unwrap());
+        circuit.run().unwrap();
+
+        let values = ["d", "e", "f", "g", "h", "i", "x", "y"].map(|wire| circuit.value(wire).unwrap());
+        assert_eq!(values, [72, 507, 492, 114, 65412, 65079, 123, 456]);
+
+        let lines = &["a -> b", "b OR c -> a", "1 -> c"];
+        assert!(matches!(day07_input(lines), Err(Error::CyclicWires(wires)) if wires.len() == 2));
+    }
+
     #[test]
     #[allow(non_snake_case)]
     fn partA() {
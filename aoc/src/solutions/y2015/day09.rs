@@ -1,46 +1,59 @@
-use std::collections::{HashMap, HashSet};
+use crate::common::{Graph, PermutationsExt};
+use crate::common::graph::tsp::{self, Objective, Route};
 
-use crate::common::PermutationsExt;
-
-fn day09<F>(lines: &[&str], f: F) -> usize
-where F: Fn(usize, usize) -> usize
-{
-    let mut edges: HashMap<(&str, &str), usize> = HashMap::new();
-    let mut nodes: HashSet<&str> = HashSet::new();
+fn distances<'a>(lines: &[&'a str]) -> Graph<&'a str, usize> {
+    let mut graph = Graph::new();
 
     for line in lines {
         let parts: Vec<_> = line.split(" ").collect();
         let (a, b, distance) = (parts[0], parts[2], parts[4].parse().unwrap());
-        nodes.insert(a);
-        nodes.insert(b);
-        edges.insert((a, b), distance);
-        edges.insert((b, a), distance);
+        graph.add_undirected_edge(a, b, distance);
     }
 
-    let nodes = nodes
-        .into_iter()
-        .collect::<Vec<_>>();
+    graph
+}
+
+fn day09(lines: &[&str], objective: Objective) -> Option<usize> {
+    let graph = distances(lines);
+
+    tsp::solve(&graph.cost_matrix(0), Route::Path, objective)
+        .map(|tour| tour.cost)
+}
+
+fn day09_permutations<F>(lines: &[&str], f: F) -> Option<usize>
+where F: Fn(usize, usize) -> usize
+{
+    let graph = distances(lines);
+    let nodes: Vec<_> = (0..graph.len()).collect();
 
     nodes.permutations()
         .map(|perm|
             perm
                 .windows(2)
-                .map(|w| edges.get(&(w[0], w[1])).unwrap())
+                .map(|w| graph.weight(w[0], w[1]).unwrap())
                 .sum()
         )
-        .into_iter()
-        .reduce(|a, b| f(a, b))
-        .unwrap()
+        .reduce(f)
 }
 
 #[crate::aoc(year = 2015, day = 9, part = "A")]
-fn day09a(lines: &[&str]) -> usize {
-    day09(lines, usize::min)
+fn day09a(lines: &[&str]) -> Option<usize> {
+    day09(lines, Objective::Minimize)
 }
 
 #[crate::aoc(year = 2015, day = 9, part = "B")]
-fn day09b(lines: &[&str]) -> usize {
-    day09(lines, usize::max)
+fn day09b(lines: &[&str]) -> Option<usize> {
+    day09(lines, Objective::Maximize)
+}
+
+#[crate::aoc(year = 2015, day = 9, part = "A", version = "permutations")]
+fn day09a_permutations(lines: &[&str]) -> Option<usize> {
+    day09_permutations(lines, usize::min)
+}
+
+#[crate::aoc(year = 2015, day = 9, part = "B", version = "permutations")]
+fn day09b_permutations(lines: &[&str]) -> Option<usize> {
+    day09_permutations(lines, usize::max)
 }
 
 #[cfg(test)]
@@ -56,12 +69,14 @@ mod tests_y2015_day09 {
     #[test]
     #[allow(non_snake_case)]
     fn partA() {
-        assert_eq!(day09a(LINES), 605);
+        assert_eq!(day09a(LINES), Some(605));
+        assert_eq!(day09a_permutations(LINES), Some(605));
     }
 
     #[test]
     #[allow(non_snake_case)]
     fn partB() {
-        assert_eq!(day09b(LINES), 982);
+        assert_eq!(day09b(LINES), Some(982));
+        assert_eq!(day09b_permutations(LINES), Some(982));
     }
 }
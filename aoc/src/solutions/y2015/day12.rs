@@ -1,51 +1,51 @@
-use crate::common::Json;
+use crate::common::{Error, Event, Json, Number, Tokenizer};
 
-fn count_numbers(json: Json, is_part_b: bool) -> i64 {
-    let mut count = 0;
-
-    match json {
-        Json::Number(n) => count += n,
-        Json::String(_) => {},
-        Json::Object(map) => {
-            if is_part_b {
-                for value in map.values() {
-                    match value {
-                        Json::String(s) => {
-                            if s == "red" {
-                                return 0;
-                            }
-                        },
-                        _ => {},
-                    }
-                }
-            }
-
-            for (_, j) in map {
-                count += count_numbers(j, is_part_b);
-            }
-        },
-        Json::Array(arr) => {
-            for j in arr {
-                count += count_numbers(j, is_part_b);
-            }
-        },
-    }
-
-    count
+/// Objects with any member whose value is `"red"`.
+fn is_red(json: &Json) -> bool {
+    json.as_object()
+        .is_some_and(|map| map.values().any(|value| value.as_str() == Some("red")))
 }
 
 fn day12(input: &[u8], is_part_b: bool) -> i64 {
     match Json::parse(input) {
-        Ok(json) => count_numbers(json, is_part_b),
+        Ok(json) => json.fold(
+            0,
+            |json| is_part_b && is_red(json),
+            |sum, json| sum + json.as_i64().unwrap_or_default(),
+        ),
         Err(e) => {
-            eprintln!("{:?}", e);
+            eprintln!("{}", e);
             -1
         },
     }
 }
 
+/// Sums the numbers straight from the token stream, without building a tree.
+fn sum_numbers(input: &[u8]) -> Result<i64, Error> {
+    let mut sum = 0;
+
+    for event in Tokenizer::new(input) {
+        if let Event::Number(raw) = event? {
+            sum += Number::parse(raw).and_then(|n| n.as_i64()).unwrap_or_default();
+        }
+    }
+
+    Ok(sum)
+}
+
 #[crate::aoc(year = 2015, day = 12, part = "A")]
 fn day12a(input: &[u8]) -> i64 {
+    match sum_numbers(input) {
+        Ok(sum) => sum,
+        Err(e) => {
+            eprintln!("{}", e);
+            -1
+        },
+    }
+}
+
+#[crate::aoc(year = 2015, day = 12, part = "A", version = "tree")]
+fn day12a_tree(input: &[u8]) -> i64 {
     day12(input, false)
 }
 
@@ -72,6 +72,8 @@ mod tests_y2015_day12 {
 
         assert_eq!(day12a(b"[]"), 0);
         assert_eq!(day12a(b"{}"), 0);
+
+        assert_eq!(day12a_tree(br#"{"a":{"b":4},"c":-1}"#), 3);
     }
 
     #[test]
@@ -1,10 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use crate::common::{Graph, PermutationsExt};
+use crate::common::graph::tsp::{self, Objective, Route};
 
-use crate::common::PermutationsExt;
-
-fn day13(lines: &[&str], is_part_b: bool) -> i64 {
-    let mut changes = HashMap::new();
-    let mut names = HashSet::new();
+fn happiness<'a>(lines: &[&'a str], is_part_b: bool) -> Graph<&'a str, i64> {
+    let mut changes = Graph::new();
 
     for &line in lines {
         let parts: Vec<_> = line.split(" ").collect();
@@ -13,20 +11,35 @@ fn day13(lines: &[&str], is_part_b: bool) -> i64 {
         let change = parts[3].parse::<i64>().unwrap()
             * if parts[2] == "lose" { -1 } else { 1 };
 
-        changes.insert((n1, n2), change);
-        names.insert(n1);
+        changes.add_edge(n1, n2, change);
     }
 
     if is_part_b {
-        for &name in &names {
-            changes.insert(("me", name), 0);
-            changes.insert((name, "me"), 0);
+        for id in 0..changes.len() {
+            let name = *changes.key(id);
+            changes.add_undirected_edge("me", name, 0);
         }
-
-        names.insert("me");
     }
 
-    let names: Vec<_> = names.into_iter().collect();
+    changes
+}
+
+fn day13(lines: &[&str], is_part_b: bool) -> Option<i64> {
+    let changes = happiness(lines, is_part_b).cost_matrix(0);
+    let len = changes.len();
+
+    // Sitting next to each other changes the happiness of both people.
+    let costs: Vec<Vec<i64>> = (0..len)
+        .map(|a| (0..len).map(|b| changes[a][b] + changes[b][a]).collect())
+        .collect();
+
+    tsp::solve(&costs, Route::Cycle, Objective::Maximize)
+        .map(|tour| tour.cost)
+}
+
+fn day13_permutations(lines: &[&str], is_part_b: bool) -> Option<i64> {
+    let changes = happiness(lines, is_part_b);
+    let names: Vec<_> = (0..changes.len()).collect();
     let len = names.len();
 
     names.permutations()
@@ -48,36 +61,44 @@ fn day13(lines: &[&str], is_part_b: bool) -> i64 {
                 let name = perm[i];
                 let next = perm[next_i];
 
-                if let Some(change) = changes.get(&(name, prev)) {
+                if let Some(change) = changes.weight(name, prev) {
                     total += change;
                 } else {
-                    println!("unknown happiness change: ({}, {})", name, prev);
+                    println!("unknown happiness change: ({}, {})", changes.key(name), changes.key(prev));
                 }
 
-                if let Some(change) = changes.get(&(name, next)) {
+                if let Some(change) = changes.weight(name, next) {
                     total += change;
                 } else {
-                    println!("unknown happiness change: ({}, {})", name, next);
+                    println!("unknown happiness change: ({}, {})", changes.key(name), changes.key(next));
                 }
             }
 
             total
         })
-        // .take(2)
         .max()
-        .unwrap()
 }
 
 #[crate::aoc(year = 2015, day = 13, part = "A")]
-fn day13a(lines: &[&str]) -> i64 {
+fn day13a(lines: &[&str]) -> Option<i64> {
     day13(lines, false)
 }
 
 #[crate::aoc(year = 2015, day = 13, part = "B")]
-fn day13b(lines: &[&str]) -> i64 {
+fn day13b(lines: &[&str]) -> Option<i64> {
     day13(lines, true)
 }
 
+#[crate::aoc(year = 2015, day = 13, part = "A", version = "permutations")]
+fn day13a_permutations(lines: &[&str]) -> Option<i64> {
+    day13_permutations(lines, false)
+}
+
+#[crate::aoc(year = 2015, day = 13, part = "B", version = "permutations")]
+fn day13b_permutations(lines: &[&str]) -> Option<i64> {
+    day13_permutations(lines, true)
+}
+
 #[cfg(test)]
 mod tests_y2015_day13 {
     use super::*;
@@ -100,6 +121,14 @@ mod tests_y2015_day13 {
     #[test]
     #[allow(non_snake_case)]
     fn partA() {
-        assert_eq!(day13a(SEATING_ARRANGEMENT), 330);
+        assert_eq!(day13a(SEATING_ARRANGEMENT), Some(330));
+        assert_eq!(day13a_permutations(SEATING_ARRANGEMENT), Some(330));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn partB() {
+        assert_eq!(day13b(SEATING_ARRANGEMENT), Some(286));
+        assert_eq!(day13b_permutations(SEATING_ARRANGEMENT), Some(286));
     }
 }
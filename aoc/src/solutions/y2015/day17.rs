@@ -1,4 +1,38 @@
-use crate::common::CombinationsExt;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::common::{Combinations, CombinationsExt};
+
+/// Number of the combinations in `combinations` that hold exactly `eggnog`.
+fn count_exact(combinations: Combinations<usize>, eggnog: usize) -> usize {
+    let mut count = 0;
+
+    combinations.for_each_ref(|comb| {
+        if comb.iter().copied().sum::<usize>() == eggnog {
+            count += 1;
+        }
+    });
+
+    count
+}
+
+#[cfg(not(feature = "parallel"))]
+fn count_of_size(containers: &[usize], n: usize, eggnog: usize) -> usize {
+    count_exact(containers.iter().copied().combinations(n), eggnog)
+}
+
+/// Splits the combinations into ranges for the rayon workers to count.
+#[cfg(feature = "parallel")]
+fn count_of_size(containers: &[usize], n: usize, eggnog: usize) -> usize {
+    containers
+        .iter()
+        .copied()
+        .combinations(n)
+        .split_into(rayon::current_num_threads() * 4)
+        .into_par_iter()
+        .map(|combinations| count_exact(combinations, eggnog))
+        .sum()
+}
 
 fn day17(containers: Vec<usize>, eggnog: usize, is_part_2: bool) -> usize {
     let mut containers = containers;
@@ -17,16 +51,10 @@ fn day17(containers: Vec<usize>, eggnog: usize, is_part_2: bool) -> usize {
     }
 
     for n in 2..=max_len {
-        let mut found = false;
-        let containers = containers.clone();
-        for comb in containers.into_iter().combinations(n) {
-            if comb.iter().sum::<usize>() == eggnog {
-                count += 1;
-                found = true;
-            }
-        }
+        let found = count_of_size(&containers, n, eggnog);
+        count += found;
 
-        if is_part_2 && found {
+        if is_part_2 && found > 0 {
             break;
         }
     }
@@ -1,129 +1,53 @@
-use std::collections::{HashMap, HashSet};
-
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Cell(i64, i64);
-
-impl Cell {
-    fn new(x: i64, y: i64) -> Self {
-        Self(x, y)
-    }
-
-    #[inline(always)]
-    fn neighbors(&self) -> Vec<Cell> {
-        let &Self(x, y) = self;
-
-        vec![
-            Self(x - 1, y - 1),
-            Self(x, y - 1),
-            Self(x + 1, y - 1),
-            Self(x - 1, y),
-            Self(x + 1, y),
-            Self(x - 1, y + 1),
-            Self(x, y + 1),
-            Self(x + 1, y + 1),
-        ]
-    }
-}
+use crate::common::Grid;
 
 struct GameOfLife {
-    width: usize,
-    height: usize,
-    cells: HashSet<Cell>,
+    lights: Grid<bool>,
 }
 
 impl GameOfLife {
-    fn cell_in_range(&self, cell: &Cell) -> bool {
-        let width = self.width as i64;
-        let height = self.height as i64;
-
-        cell.0 >= 0 && cell.0 < width && cell.1 >= 0 && cell.1 < height
-    }
-
     fn stick_on_corners(&mut self) {
-        let x = (self.width as i64) - 1;
-        let y = (self.height as i64) - 1;
-        self.cells.insert(Cell::new(0, 0));
-        self.cells.insert(Cell::new(x, 0));
-        self.cells.insert(Cell::new(0, y));
-        self.cells.insert(Cell::new(x, y));
-    }
+        if self.lights.is_empty() {
+            return;
+        }
 
-    fn neighbor_counts(&self) -> HashMap<Cell, usize> {
-        let mut counts = HashMap::new();
+        let x = self.lights.width() - 1;
+        let y = self.lights.height() - 1;
 
-        for cell in self.cells.iter()
-            .flat_map(Cell::neighbors)
-            .filter(|cell| self.cell_in_range(cell))
-        {
-            *counts.entry(cell).or_insert(0) += 1;
+        for corner in [(0, 0), (x, 0), (0, y), (x, y)] {
+            self.lights[corner] = true;
         }
-
-        counts
     }
 
     fn next_generation(&mut self) {
-        let mut gen = self.neighbor_counts()
-            .into_iter()
-            .filter_map(|(cell, count)| {
-                match (self.cells.contains(&cell), count) {
-                    (true, 2) | (_, 3) => Some(cell.clone()),
-                    _ => None,
-                }
-            })
-            .collect();
+        let lights = &self.lights;
 
-        std::mem::swap(&mut self.cells, &mut gen);
+        self.lights = Grid::from_fn(lights.width(), lights.height(), |x, y| {
+            let count = lights
+                .neighbors8(x, y)
+                .filter(|&neighbor| lights[neighbor])
+                .count();
+
+            matches!((lights[(x, y)], count), (true, 2) | (_, 3))
+        });
     }
 
     fn living_cells(&self) -> usize {
-        self.cells.len()
+        self.lights.count(|&on| on)
     }
 }
 
 impl std::fmt::Display for GameOfLife {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let cell = Cell::new(x as i64, y as i64);
-                if self.cells.contains(&cell) {
-                    write!(f, "#")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-
-            writeln!(f)?;
-        }
-
-        Ok(())
+        writeln!(f, "{}", self.lights.display_with(|&on| if on { '#' } else { '.' }))
     }
 }
 
 impl std::str::FromStr for GameOfLife {
-    type Err = ();
+    type Err = crate::common::grid::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut width = 0;
-        let mut height = 0;
-        let mut cells = HashSet::new();
-
-        for (y, line) in s.lines().enumerate() {
-            let line_width = line.len();
-
-            for (x, c) in line.bytes().enumerate() {
-                if c == b'#' {
-                    cells.insert(Cell::new(x as i64, y as i64));
-                }
-            }
-
-            width = std::cmp::max(width, line_width);
-            height += 1;
-        }
-
         Ok(Self {
-            width,
-            height,
-            cells,
+            lights: Grid::parse(s, |c| c == b'#')?,
         })
     }
 }
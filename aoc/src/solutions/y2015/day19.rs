@@ -63,73 +63,64 @@ impl std::str::FromStr for Input {
     }
 }
 
-#[crate::aoc(year = 2015, day = 19, part = "A")]
-fn day19a(input: &str) -> usize {
-    match input.parse() {
-        Ok(Input { replacements, original }) => {
-            let mut molecules: HashSet<String> = HashSet::new();
-
-            for Replacement(from, to) in replacements {
-                for (i, _) in original.match_indices(&from) {
-                    let mut molecule = String::new();
-                    molecule.push_str(&original[..i]);
-                    molecule.push_str(&to);
-                    molecule.push_str(&original[i + from.len()..]);
-                    molecules.insert(molecule);
-                }
-            }
+#[crate::aoc_generator(year = 2015, day = 19)]
+fn day19_input(input: &str) -> Result<Input, Error> {
+    input.parse()
+}
 
-            molecules.len()
-        },
-        Err(e) => {
-            eprintln!("error: {}", e);
-            0
-        },
+#[crate::aoc(year = 2015, day = 19, part = "A", generator)]
+fn day19a(input: &Input) -> usize {
+    let Input { replacements, original } = input;
+    let mut molecules: HashSet<String> = HashSet::new();
+
+    for Replacement(from, to) in replacements {
+        for (i, _) in original.match_indices(from) {
+            let mut molecule = String::new();
+            molecule.push_str(&original[..i]);
+            molecule.push_str(to);
+            molecule.push_str(&original[i + from.len()..]);
+            molecules.insert(molecule);
+        }
     }
+
+    molecules.len()
 }
 
-#[crate::aoc(year = 2015, day = 19, part = "B", version = "do_the_work")]
-fn day19b_do_the_work(input: &str) -> usize {
+#[crate::aoc(year = 2015, day = 19, part = "B", version = "do_the_work", generator)]
+fn day19b_do_the_work(input: &Input) -> usize {
     // For posterity: This works for the example inputs, but stalls on
     // the actual input. I suspect I could use a queue to repeatedly test all
     // substitutions from original => 'e', but that would likely take an
     // exceedingly long time, and the below method has worked for the past 8 years, so...
 
-    match input.parse() {
-        Ok(Input { mut replacements, mut original }) => {
-            replacements.sort_unstable_by(|a, b| b.1.len().partial_cmp(&a.1.len()).unwrap());
-            let mut steps = 0;
-            let end = String::from("e");
-
-            while original != end {
-                for Replacement(from, to) in &replacements {
-                    let mut replacement_found: Option<String> = None;
-
-                    for (i, _) in original.match_indices(to) {
-                        let mut molecule = String::new();
-                        molecule.push_str(&original[..i]);
-                        molecule.push_str(&from);
-                        molecule.push_str(&original[i + to.len()..]);
-
-                        replacement_found = Some(molecule);
-                        break;
-                    }
-
-                    if let Some(replacement) = replacement_found {
-                        original = replacement;
-                        steps += 1;
-                        break;
-                    }
-                }
+    let mut replacements = input.replacements.iter().collect::<Vec<_>>();
+    let mut original = input.original.clone();
+    replacements.sort_unstable_by(|a, b| b.1.len().partial_cmp(&a.1.len()).unwrap());
+    let mut steps = 0;
+    let end = String::from("e");
+
+    while original != end {
+        for Replacement(from, to) in &replacements {
+            let mut replacement_found: Option<String> = None;
+
+            if let Some((i, _)) = original.match_indices(to.as_str()).next() {
+                let mut molecule = String::new();
+                molecule.push_str(&original[..i]);
+                molecule.push_str(from);
+                molecule.push_str(&original[i + to.len()..]);
+
+                replacement_found = Some(molecule);
             }
 
-            steps
-        },
-        Err(e) => {
-            eprintln!("error: {}", e);
-            0
-        },
+            if let Some(replacement) = replacement_found {
+                original = replacement;
+                steps += 1;
+                break;
+            }
+        }
     }
+
+    steps
 }
 
 #[crate::aoc(year = 2015, day = 19, part = "B")]
@@ -147,18 +138,35 @@ fn day19b(input: &str) -> usize {
 mod tests_y2015_day19 {
     use super::*;
 
+    fn parse(input: &str) -> Input {
+        input.parse().unwrap()
+    }
+
     #[test]
     #[allow(non_snake_case)]
     fn partA() {
-        assert_eq!(day19a("H => HO\nH => OH\nO => HH\n\n\nHOH"), 4);
-        assert_eq!(day19a("H => HO\nH => OH\nO => HH\n\n\nHOHOHO"), 7);
-        assert_eq!(day19a("H => 00\n\n\nH2O"), 1);
+        assert_eq!(day19a(&parse("H => HO\nH => OH\nO => HH\n\n\nHOH")), 4);
+        assert_eq!(day19a(&parse("H => HO\nH => OH\nO => HH\n\n\nHOHOHO")), 7);
+        assert_eq!(day19a(&parse("H => 00\n\n\nH2O")), 1);
+    }
+
+    #[test]
+    fn generated_once() {
+        use crate::input::with_input;
+
+        let generate = || generator_y2015_day19(&mut crate::Timings::default()).unwrap();
+        let (first, second) = with_input(b"H => HO\n\nHOH", || (generate(), generate()));
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+        let other = with_input(b"H => OH\n\nHOH", generate);
+        assert!(!std::sync::Arc::ptr_eq(&first, &other));
+        assert_eq!(other.replacements.len(), 1);
     }
 
     #[test]
     #[allow(non_snake_case)]
     fn partB() {
-        assert_eq!(day19b_do_the_work("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOH"), 3);
-        assert_eq!(day19b_do_the_work("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO"), 6);
+        assert_eq!(day19b_do_the_work(&parse("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOH")), 3);
+        assert_eq!(day19b_do_the_work(&parse("e => H\ne => O\nH => HO\nH => OH\nO => HH\n\nHOHOHO")), 6);
     }
 }
@@ -43,6 +43,12 @@ impl std::str::FromStr for Character {
     }
 }
 
+impl<'a> crate::input::FromInput<'a> for Character {
+    fn from_input(input: &'a [u8], _: Option<&str>) -> Result<Self, crate::error::Error> {
+        crate::input::parse_str(input)
+    }
+}
+
 fn does_player_win(player: &Character, boss: &Character) -> bool {
     let a = ((player.hp as f32) / std::cmp::max(1, boss.damage.saturating_sub(player.armor)) as f32).ceil();
     let b = ((boss.hp as f32) / std::cmp::max(1, player.damage.saturating_sub(boss.armor)) as f32).ceil();
@@ -85,55 +91,42 @@ const RINGS: [Item; 8] = [
     Item { cost: 80, damage: 0, armor: 3 }, // Defense +3
 ];
 
-fn day21<F1, F2>(input: &str, initial: u32, determine_actual_winner: F1, cmp: F2) -> Option<u32>
+fn day21<F1, F2>(boss: &Character, initial: u32, determine_actual_winner: F1, cmp: F2) -> u32
 where
     F1: Fn(bool) -> bool,
     F2: Fn(u32, u32) -> u32,
 {
-    match input.trim().parse::<Character>() {
-        Ok(boss) => {
-            let mut ret_cost = initial;
-
-            for wi in 0..WEAPONS.len() {
-                for ai in 0..ARMORS.len() {
-                    for ri1 in 0..RINGS.len() - 1 {
-                        for ri2 in ri1 + 1..RINGS.len() {
-                            let weapon = &WEAPONS[wi];
-                            let armor = &ARMORS[ai];
-                            let ring1 = &RINGS[ri1];
-                            let ring2 = &RINGS[ri2];
-
-                            let cost = weapon.cost + armor.cost + ring1.cost + ring2.cost;
-                            let damage = weapon.damage + armor.damage + ring1.damage + ring2.damage;
-                            let armor = weapon.armor + armor.armor + ring1.armor + ring2.armor;
-
-                            let player = Character {
-                                hp: 100,
-                                damage,
-                                armor,
-                            };
-
-                            if determine_actual_winner(does_player_win(&player, &boss)) {
-                                ret_cost = cmp(ret_cost, cost)
-                            }
-                        }
+    let mut ret_cost = initial;
+
+    for weapon in WEAPONS.iter() {
+        for armor in ARMORS.iter() {
+            for (ri1, ring1) in RINGS.iter().enumerate() {
+                for ring2 in RINGS[ri1 + 1..].iter() {
+                    let cost = weapon.cost + armor.cost + ring1.cost + ring2.cost;
+                    let damage = weapon.damage + armor.damage + ring1.damage + ring2.damage;
+                    let armor = weapon.armor + armor.armor + ring1.armor + ring2.armor;
+
+                    let player = Character {
+                        hp: 100,
+                        damage,
+                        armor,
+                    };
+
+                    if determine_actual_winner(does_player_win(&player, boss)) {
+                        ret_cost = cmp(ret_cost, cost)
                     }
                 }
             }
-
-            Some(ret_cost)
-        },
-        Err(e) => {
-            eprintln!("error: {}", e);
-            None
         }
     }
+
+    ret_cost
 }
 
 #[crate::aoc(year = 2015, day = 21, part = "A")]
-fn day21a(input: &str) -> Option<u32> {
+fn day21a(boss: &Character) -> u32 {
     day21(
-        input,
+        boss,
         u32::MAX,
         |winner| winner,
         std::cmp::min,
@@ -141,9 +134,9 @@ fn day21a(input: &str) -> Option<u32> {
 }
 
 #[crate::aoc(year = 2015, day = 21, part = "B")]
-fn day21b(input: &str) -> Option<u32> {
+fn day21b(boss: &Character) -> u32 {
     day21(
-        input,
+        boss,
         u32::MIN,
         |winner| !winner,
         std::cmp::max,
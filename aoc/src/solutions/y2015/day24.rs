@@ -1,22 +1,46 @@
-use crate::common::CombinationsExt;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-fn day24(packages: Vec<u64>, num_compartments: u64) -> Option<u64> {
-    let mut result = u64::MAX;
-    let compartment_weight = packages.clone().into_iter().sum::<u64>() / num_compartments;
-
-    for i in 1..(packages.len() - num_compartments as usize + 1) {
-        for comb in packages.iter().combinations(i) {
-            if comb.clone().into_iter().sum::<u64>() == compartment_weight {
-                result = std::cmp::min(result, comb.into_iter().product());
-            }
-        }
+use crate::common::{Combinations, CombinationsExt};
+
+/// Lowest quantum entanglement of the groups in `groups` that weigh exactly
+/// `weight`.
+fn lowest_entanglement(groups: Combinations<u64>, weight: u64) -> Option<u64> {
+    let mut result = None;
 
-        if result < u64::MAX {
-            break;
+    groups.for_each_ref(|comb| {
+        if comb.iter().copied().sum::<u64>() == weight {
+            let product = comb.iter().copied().product();
+            result = Some(result.map_or(product, |result: u64| result.min(product)));
         }
-    }
+    });
+
+    result
+}
+
+#[cfg(not(feature = "parallel"))]
+fn smallest_group(packages: &[u64], size: usize, weight: u64) -> Option<u64> {
+    lowest_entanglement(packages.iter().copied().combinations(size), weight)
+}
+
+/// Splits the groups into ranges for the rayon workers to search.
+#[cfg(feature = "parallel")]
+fn smallest_group(packages: &[u64], size: usize, weight: u64) -> Option<u64> {
+    packages
+        .iter()
+        .copied()
+        .combinations(size)
+        .split_into(rayon::current_num_threads() * 4)
+        .into_par_iter()
+        .filter_map(|groups| lowest_entanglement(groups, weight))
+        .min()
+}
+
+fn day24(packages: Vec<u64>, num_compartments: u64) -> Option<u64> {
+    let compartment_weight = packages.iter().sum::<u64>() / num_compartments;
 
-    Some(result)
+    (1..(packages.len() - num_compartments as usize + 1))
+        .find_map(|size| smallest_group(&packages, size, compartment_weight))
 }
 
 #[crate::aoc(year = 2015, day = 24, part = "A")]
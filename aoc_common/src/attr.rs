@@ -0,0 +1,244 @@
+//! Arguments of the `aoc` and `aoc_generator` attributes.
+//!
+//! Shared by `aoc_derive`, which expands the attributes, and the `aoc` build
+//! script, which finds them to register every solution.
+
+use std::str::FromStr;
+
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    Lit, LitInt, LitStr, Token,
+};
+
+use crate::Part;
+
+mod kw {
+    syn::custom_keyword!(year);
+    syn::custom_keyword!(day);
+    syn::custom_keyword!(part);
+    syn::custom_keyword!(version);
+    syn::custom_keyword!(separator);
+    syn::custom_keyword!(generator);
+    syn::custom_keyword!(example);
+}
+
+/// Example input for a solution, with the answer it should give.
+pub struct Example {
+    pub input: String,
+    pub expected: String,
+}
+
+impl Parse for Example {
+    /// Parse `"input" => answer`, where the answer is a string, integer, or
+    /// negative integer literal.
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let example = input.parse::<LitStr>()?.value();
+        input.parse::<Token![=>]>()?;
+
+        let sign = match input.parse::<Option<Token![-]>>()? {
+            Some(_) => "-",
+            None => "",
+        };
+        let expected = match input.parse::<Lit>()? {
+            Lit::Str(s) if sign.is_empty() => s.value(),
+            Lit::Int(n) => format!("{sign}{}", n.base10_digits()),
+            lit => return Err(syn::Error::new(lit.span(), "expected a string or integer answer")),
+        };
+
+        Ok(Example {
+            input: example,
+            expected,
+        })
+    }
+}
+
+/// Arguments of the `aoc` attribute.
+pub struct AocEntry {
+    pub year: usize,
+    pub day: usize,
+    pub part: Part,
+    pub version: Option<String>,
+    pub separator: Option<String>,
+    pub generator: bool,
+    pub examples: Vec<Example>,
+}
+
+impl Parse for AocEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut year: Option<usize> = None;
+        let mut day: Option<usize> = None;
+        let mut part: Option<Part> = None;
+        let mut version: Option<String> = None;
+        let mut separator: Option<String> = None;
+        let mut generator = false;
+        let mut examples = Vec::new();
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::year) {
+                input.parse::<kw::year>()?;
+                input.parse::<Token![=]>()?;
+                year = Some(input.parse::<LitInt>()?.base10_parse()?);
+            } else if lookahead.peek(kw::day) {
+                input.parse::<kw::day>()?;
+                input.parse::<Token![=]>()?;
+                day = Some(input.parse::<LitInt>()?.base10_parse()?);
+            } else if lookahead.peek(kw::part) {
+                input.parse::<kw::part>()?;
+                input.parse::<Token![=]>()?;
+                let p = input.parse::<LitStr>()?;
+                match Part::from_str(&p.value()) {
+                    Ok(p) => part = Some(p),
+                    Err(_) => {
+                        return Err(syn::Error::new(p.span(), format!("expected A|B, found: {}", p.value())));
+                    },
+                }
+            } else if lookahead.peek(kw::version) {
+                input.parse::<kw::version>()?;
+                input.parse::<Token![=]>()?;
+                version = Some(input.parse::<LitStr>()?.value());
+            } else if lookahead.peek(kw::separator) {
+                input.parse::<kw::separator>()?;
+                input.parse::<Token![=]>()?;
+                separator = Some(input.parse::<LitStr>()?.value());
+            } else if lookahead.peek(kw::generator) {
+                input.parse::<kw::generator>()?;
+                generator = true;
+            } else if lookahead.peek(kw::example) {
+                input.parse::<kw::example>()?;
+                let content;
+                parenthesized!(content in input);
+                examples.push(content.parse()?);
+                if !content.is_empty() {
+                    return Err(content.error("expected a single example"));
+                }
+            } else {
+                return Err(lookahead.error());
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(AocEntry {
+            year: year.ok_or_else(|| input.error("year must be supplied"))?,
+            day: day.ok_or_else(|| input.error("day must be supplied"))?,
+            part: part.ok_or_else(|| input.error("part must be supplied"))?,
+            version,
+            separator,
+            generator,
+            examples,
+        })
+    }
+}
+
+impl AocEntry {
+    /// Name of the runner function generated for this solution.
+    pub fn runner_name(&self) -> String {
+        format!(
+            "runner_y{}_day{:02}_part{}_{}",
+            self.year,
+            self.day,
+            self.part,
+            self.version.as_deref().map_or_else(|| String::from("none"), version_ident),
+        )
+    }
+}
+
+/// Arguments of the `aoc_generator` attribute.
+pub struct GeneratorEntry {
+    pub year: usize,
+    pub day: usize,
+    pub separator: Option<String>,
+}
+
+impl Parse for GeneratorEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut year: Option<usize> = None;
+        let mut day: Option<usize> = None;
+        let mut separator: Option<String> = None;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::year) {
+                input.parse::<kw::year>()?;
+                input.parse::<Token![=]>()?;
+                year = Some(input.parse::<LitInt>()?.base10_parse()?);
+            } else if lookahead.peek(kw::day) {
+                input.parse::<kw::day>()?;
+                input.parse::<Token![=]>()?;
+                day = Some(input.parse::<LitInt>()?.base10_parse()?);
+            } else if lookahead.peek(kw::separator) {
+                input.parse::<kw::separator>()?;
+                input.parse::<Token![=]>()?;
+                separator = Some(input.parse::<LitStr>()?.value());
+            } else {
+                return Err(lookahead.error());
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(GeneratorEntry {
+            year: year.ok_or_else(|| input.error("year must be supplied"))?,
+            day: day.ok_or_else(|| input.error("day must be supplied"))?,
+            separator,
+        })
+    }
+}
+
+/// Version name as part of an identifier, with every character that can't be
+/// used in an identifier replaced by `_`.
+pub fn version_ident(version: &str) -> String {
+    version
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_aoc_entry() {
+        let entry: AocEntry = syn::parse_str(r#"year = 2015, day = 19, part = "B", version = "do the-work", generator"#).unwrap();
+        assert_eq!((entry.year, entry.day, entry.part), (2015, 19, Part::B));
+        assert_eq!(entry.version.as_deref(), Some("do the-work"));
+        assert!(entry.generator);
+        assert_eq!(entry.runner_name(), "runner_y2015_day19_partB_do_the_work");
+
+        let entry: AocEntry = syn::parse_str(r#"year = 2015, day = 1, part = "A", separator = "\n""#).unwrap();
+        assert_eq!(entry.separator.as_deref(), Some("\n"));
+        assert_eq!(entry.runner_name(), "runner_y2015_day01_partA_none");
+
+        let entry: AocEntry = syn::parse_str(r#"year = 2015, day = 3, part = "A", example("^>v<" => 4), example(">" => "2"), example("v" => -1)"#).unwrap();
+        let examples = entry.examples
+            .iter()
+            .map(|e| (e.input.as_str(), e.expected.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(examples, [("^>v<", "4"), (">", "2"), ("v", "-1")]);
+
+        let err = |s| syn::parse_str::<AocEntry>(s).err().map(|e| e.to_string());
+        assert_eq!(err(r#"year = 2015, day = 1"#).as_deref(), Some("unexpected end of input, part must be supplied"));
+        assert_eq!(err(r#"year = 2015, day = 1, part = "C""#).as_deref(), Some("expected A|B, found: C"));
+        assert!(err(r#"year = 2015 day = 1, part = "A""#).is_some());
+        assert!(err(r#"year = 2015, day = 1, part = "A", speed = 3"#).is_some());
+        assert!(err(r#"year = 2015, day = 1, part = "A", example("x" => 1.5)"#).is_some());
+        assert!(err(r#"year = 2015, day = 1, part = "A", example("x" => -"1")"#).is_some());
+        assert!(err(r#"year = 2015, day = 1, part = "A", example("x" => 1, "y" => 2)"#).is_some());
+    }
+
+    #[test]
+    fn parse_generator_entry() {
+        let entry: GeneratorEntry = syn::parse_str("year = 2015, day = 19").unwrap();
+        assert_eq!((entry.year, entry.day, entry.separator), (2015, 19, None));
+
+        assert!(syn::parse_str::<GeneratorEntry>("year = 2015").is_err());
+        assert!(syn::parse_str::<GeneratorEntry>(r#"year = 2015, day = 19, part = "A""#).is_err());
+    }
+}
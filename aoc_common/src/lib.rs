@@ -1,3 +1,6 @@
+#[cfg(feature = "attr")]
+pub mod attr;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
 pub enum Part {
     A,
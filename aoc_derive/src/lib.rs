@@ -1,84 +1,88 @@
-use std::str::FromStr;
-
+use aoc_common::attr::{AocEntry, GeneratorEntry};
 use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use proc_macro_error::{abort, proc_macro_error};
 use quote::{quote, ToTokens};
 use syn::{
-    parse::{Parse, ParseStream},
-    parse_macro_input,
-    FnArg, Ident, ItemFn,
-    LitInt, LitStr,
-    PatType, ReturnType, Token,
+    parse_macro_input, parse_quote,
+    FnArg, GenericArgument, Ident, ItemFn,
+    PatType, PathArguments, ReturnType, Signature,
+    Type, TypePath, TypeReference, TypeSlice,
 };
 
-mod kw {
-    syn::custom_keyword!(year);
-    syn::custom_keyword!(day);
-    syn::custom_keyword!(part);
-    syn::custom_keyword!(version);
-    syn::custom_keyword!(separator);
+/// Name of the function generated by `aoc_generator` for a given year and day.
+fn generator_name(year: usize, day: usize) -> Ident {
+    Ident::new(&format!("generator_y{year}_day{day:02}"), Span::call_site())
 }
 
-struct AocEntry {
-    year: usize,
-    day: usize,
-    part: aoc_common::Part,
-    version: Option<String>,
-    separator: Option<String>,
+/// Build the statement that loads the puzzle input into `input`, and an
+/// expression converting `input` into an argument of type `ty` with
+/// `crate::input::FromInput`.
+fn input_arg(year: usize, day: usize, separator: Option<String>, ty: Option<&Type>) -> (TokenStream2, TokenStream2) {
+    let Some(ty) = ty else {
+        return (quote!(), quote!());
+    };
+    let sep = match separator {
+        Some(sep) => quote!(Some(#sep)),
+        None => quote!(None),
+    };
+    let from_input = |ty: &Type| quote!(<#ty as crate::input::FromInput>::from_input(&input, #sep)?);
+
+    let arg = match ty {
+        // `&str` and `&[u8]` borrow the input directly
+        Type::Reference(TypeReference { mutability: None, elem, .. }) if is_str_or_bytes(elem) => from_input(ty),
+        // Other references borrow a value built from the input, with slices built as `Vec`s
+        Type::Reference(TypeReference { mutability, elem, .. }) => {
+            let built = match elem.as_ref() {
+                Type::Slice(TypeSlice { elem, .. }) => from_input(&parse_quote!(Vec<#elem>)),
+                elem => from_input(elem),
+            };
+
+            quote!(&#mutability #built)
+        },
+        ty => from_input(ty),
+    };
+
+    (quote!(let input = crate::input::input_bytes(#year, #day)?;), arg)
 }
 
-impl Parse for AocEntry {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut year: Option<usize> = None;
-        let mut day: Option<usize> = None;
-        let mut part: Option<aoc_common::Part> = None;
-        let mut version: Option<String> = None;
-        let mut separator: Option<String> = None;
-
-        while !input.is_empty() {
-            let lookahead = input.lookahead1();
-            if lookahead.peek(kw::year) {
-                input.parse::<kw::year>()?;
-                input.parse::<Token![=]>()?;
-                year = Some(input.parse::<LitInt>()?.base10_parse()?);
-            } else if lookahead.peek(kw::day) {
-                input.parse::<kw::day>()?;
-                input.parse::<Token![=]>()?;
-                day = Some(input.parse::<LitInt>()?.base10_parse()?);
-            } else if lookahead.peek(kw::part) {
-                input.parse::<kw::part>()?;
-                input.parse::<Token![=]>()?;
-                let p = input.parse::<LitStr>()?;
-                match aoc_common::Part::from_str(&p.value()) {
-                    Ok(p) => part = Some(p),
-                    Err(_) => {
-                        return Err(input.error(format!("expected A|B, found: {}", p.value())));
-                    },
-                }
-            } else if lookahead.peek(kw::version) {
-                input.parse::<kw::version>()?;
-                input.parse::<Token![=]>()?;
-                version = Some(input.parse::<LitStr>()?.value());
-            } else if lookahead.peek(kw::separator) {
-                input.parse::<kw::separator>()?;
-                input.parse::<Token![=]>()?;
-                separator = Some(input.parse::<LitStr>()?.value());
-            } else {
-                return Err(lookahead.error());
-            }
-
-            if input.peek(Token![,]) {
-                input.parse::<Token![,]>()?;
-            }
-        }
+/// Whether `ty` is `str` or `[u8]`.
+fn is_str_or_bytes(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { path, .. }) => path.is_ident("str"),
+        Type::Slice(TypeSlice { elem, .. }) => matches!(
+            elem.as_ref(),
+            Type::Path(TypePath { path, .. }) if path.is_ident("u8"),
+        ),
+        _ => false,
+    }
+}
 
-        Ok(AocEntry {
-            year: year.expect("year must be supplied"),
-            day: day.expect("day must be supplied"),
-            part: part.expect("part must be supplied"),
-            version,
-            separator,
-        })
+/// Type of the first argument of `sig`, if it has one.
+fn first_arg_type(sig: &Signature) -> Option<&Type> {
+    match sig.inputs.first() {
+        Some(FnArg::Typed(PatType { ty, .. })) => Some(ty),
+        _ => None,
+    }
+}
+
+/// If `ty` is `Option<T>` or `Result<T, ..>`, get the wrapper name and `T`.
+fn unwrap_type(ty: &Type) -> Option<(String, &Type)> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let wrapper = segment.ident.to_string();
+    if wrapper != "Option" && wrapper != "Result" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(inner)) => Some((wrapper, inner)),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -95,73 +99,101 @@ impl Parse for AocEntry {
 /// * `version` -> string (optional)
 /// * `separator` -> string (optional)
 ///
-/// with `integer` being equivalent to regex `\d+` and `string`
-/// being equivalent to regex `"[a-zA-Z0-9_\s\\]+"`.
+/// with `integer` being an integer literal and `string` a string literal.
+/// Characters of the `version` that can't be used in an identifier are
+/// replaced with `_` in the name of the runnable function.
+///
+/// The solution's argument is built from the puzzle input with
+/// `crate::input::FromInput`, so any type implementing that trait can be
+/// taken by value, or by reference. The `separator` is passed on to
+/// `FromInput::from_input`.
+///
+/// Any number of `example("input" => answer)` arguments, with `answer` a
+/// string or integer, declare example inputs and the answers the solution
+/// should give for them. The `aoc` build script turns each one into a test.
+///
+/// The bare key `generator` makes the solution take the value produced by
+/// the [macro@aoc_generator] for the same year and day, either by value or by
+/// reference, instead of the puzzle input. The value is shared with other
+/// solutions for the same input, so taking it by value or by `&mut` clones
+/// it, and needs it to implement `Clone`.
 ///
-/// TODO: Enumerate allowed argument and return types.
+/// The runnable function records how long it took to load the input, to
+/// convert it into the solution's argument, and to run the solution.
+///
+/// TODO: Enumerate allowed return types.
 pub fn aoc(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let entry = parse_macro_input!(attr as AocEntry);
+    let runner_name = entry.runner_name();
     let AocEntry {
         year,
         day,
         part,
         version,
         separator,
-    } = parse_macro_input!(attr as AocEntry);
+        generator,
+        examples: _,
+    } = entry;
     let part = part.to_string();
 
     let func = parse_macro_input!(input as ItemFn);
     let func_name = func.sig.ident.clone();
-    let runner_func_name = Ident::new(
-        &format!(
-            "runner_y{year}_day{day:02}_part{part}_{}",
-            version.clone().map_or_else(|| String::from("none"), |v| v.replace(" ", "_")),
-        ),
-        func.sig.ident.span(),
-    );
-    let sep = match separator {
-        Some(sep) => quote!(Some(#sep)),
-        None => quote!(None),
-    };
+    let runner_func_name = Ident::new(&runner_name, func.sig.ident.span());
 
-    // TODO: Reevaluate which of these I need to keep
-    // I can probably get rid of the &mut inputs
-    let inputs = match func.sig.inputs.first() {
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("Vec < & [u8] >") => {
-            quote!((crate::input::parse_input_bytes(&crate::input::input_bytes(#year, #day)?, #sep.map(|c: char| c as u8))?))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("& [& [u8]]") => {
-            quote!((&crate::input::parse_input_bytes(&crate::input::input_bytes(#year, #day)?, #sep.map(|c: char| c as u8))?))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("Vec < & str >") => {
-            quote!((crate::input::input_string(#year, #day)?.lines().collect()))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("& [& str]") => {
-            quote!((&crate::input::input_string(#year, #day)?.lines().collect::<Vec<_>>()))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("& mut [& str]") => {
-            quote!((&mut crate::input::input_string(#year, #day)?.lines().collect::<Vec<_>>()))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("& str") => {
-            quote!((&crate::input::input_string(#year, #day)?))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("& [u8]") => {
-            quote!((&crate::input::input_bytes(#year, #day)?))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("Vec <") => {
-            quote!((crate::input::parse_input(&crate::input::input_string(#year, #day)?, #sep)?))
-        },
-        Some(FnArg::Typed(PatType { ty, .. })) if quote!(#ty).to_string().contains("& mut [") => {
-            quote!((&mut crate::input::parse_input(&crate::input::input_string(#year, #day)?, #sep)?))
-        },
-        Some(_) => {
-            quote!((&crate::input::parse_input(&crate::input::input_string(#year, #day)?, #sep)?))
-        },
-        None => quote!(()),
+    let (load, generate) = if generator {
+        let generator_func_name = generator_name(year, day);
+        // The generator shares one value between runs, so solutions that
+        // take it by value or mutably get their own clone.
+        let arg = match first_arg_type(&func.sig) {
+            Some(Type::Reference(TypeReference { mutability: Some(_), .. })) => quote! {
+                let mut generated = (*generated).clone();
+                let arg = &mut generated;
+            },
+            Some(Type::Reference(_)) => quote!(let arg = &*generated;),
+            Some(_) => quote!(let arg = ::std::sync::Arc::unwrap_or_clone(generated);),
+            None => abort!(func.sig, "AOC solution using a generator must take an argument"),
+        };
+
+        (
+            quote!(let generated = #generator_func_name(timings)?;),
+            quote! {
+                let start = ::std::time::Instant::now();
+                #arg
+                timings.generate += start.elapsed();
+            },
+        )
+    } else {
+        let (load, arg) = input_arg(year, day, separator, first_arg_type(&func.sig));
+        let arg = match arg.is_empty() {
+            true => quote!(),
+            false => quote!(let arg = #arg;),
+        };
+        let load = quote! {
+            let start = ::std::time::Instant::now();
+            #load
+            timings.load = start.elapsed();
+        };
+        let generate = quote! {
+            let start = ::std::time::Instant::now();
+            #arg
+            timings.generate = start.elapsed();
+        };
+
+        (load, generate)
+    };
+    let call = match func.sig.inputs.is_empty() {
+        true => quote!(#func_name()),
+        false => quote!(#func_name(arg)),
+    };
+    let solve = quote! {
+        let start = ::std::time::Instant::now();
+        let result = #call;
+        timings.solve = start.elapsed();
     };
 
-    let (call, ty) = match func.sig.output {
+    let (output, ty) = match func.sig.output {
         ReturnType::Type(_, ref t) if t.to_token_stream().to_string().contains("Result < ") => {
-            (quote!(#func_name #inputs), quote!(#t))
+            (quote!(result), quote!(#t))
         },
         ReturnType::Type(_, ref t) if t.to_token_stream().to_string().contains("Option < ") => {
             let no_option = t
@@ -169,14 +201,14 @@ pub fn aoc(attr: TokenStream, input: TokenStream) -> TokenStream {
                 .into_iter()
                 .skip(1)
                 .collect::<proc_macro2::TokenStream>();
-            let version = version.map_or(quote!(None), |v| quote!(Some(#v)));
+            let version = version.map_or(quote!(None), |v| quote!(Some(#v.to_string())));
 
             (
-                quote!(Ok(#func_name #inputs .ok_or(crate::error::Error::NoOutput(#year, #day, #part.to_string(), #version))?)),
+                quote!(Ok(result.ok_or(crate::error::Error::NoOutput(#year, #day, #part.to_string(), #version))?)),
                 quote!(::eyre::Result #no_option),
             )
         },
-        ReturnType::Type(_, ref t) => (quote!(Ok(#func_name #inputs)), quote!(::eyre::Result<#t>)),
+        ReturnType::Type(_, ref t) => (quote!(Ok(result)), quote!(::eyre::Result<#t>)),
         _ => abort!(func.sig, "AOC solution cannot return ()"),
     };
 
@@ -184,8 +216,90 @@ pub fn aoc(attr: TokenStream, input: TokenStream) -> TokenStream {
         #func
 
         #[allow(non_snake_case)]
-        pub fn #runner_func_name() -> #ty {
-            #call
+        pub fn #runner_func_name(timings: &mut crate::Timings) -> #ty {
+            #load
+            #generate
+            #solve
+            #output
+        }
+    }.into()
+}
+
+#[proc_macro_attribute]
+#[proc_macro_error]
+/// Create a generator for an Advent of Code day, which converts the puzzle
+/// input into a value that is shared by solutions declared with the
+/// `generator` key in [macro@aoc].
+///
+/// Attributes are key -> value pairs:
+///
+/// * `year` -> integer
+/// * `day` -> integer
+/// * `separator` -> string (optional)
+///
+/// The generator takes the puzzle input in any of the forms accepted by
+/// [macro@aoc], and returns `T`, `Option<T>` or `Result<T, E>`.
+///
+/// The generated value is kept, along with the input it was generated from,
+/// so running both parts of a day only converts the input once. It's
+/// generated again if the input changes, such as for each example. `T` must
+/// be `Send + Sync + 'static` to be kept.
+pub fn aoc_generator(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let GeneratorEntry {
+        year,
+        day,
+        separator,
+    } = parse_macro_input!(attr as GeneratorEntry);
+
+    let func = parse_macro_input!(input as ItemFn);
+    let func_name = func.sig.ident.clone();
+    let generator_func_name = generator_name(year, day);
+    let (load, arg) = input_arg(year, day, separator, first_arg_type(&func.sig));
+    // The input is the key of the generated value, so it's needed even if
+    // the generator doesn't take it.
+    let load = match load.is_empty() {
+        true => quote!(let input: Vec<u8> = Vec::new();),
+        false => load,
+    };
+
+    let (output, ty) = match func.sig.output {
+        ReturnType::Type(_, ref t) => match unwrap_type(t) {
+            Some((wrapper, inner)) if wrapper == "Option" => (
+                quote!(generated.ok_or(crate::error::Error::NoGeneratedInput(#year, #day))?),
+                quote!(#inner),
+            ),
+            Some((_, inner)) => (quote!(generated?), quote!(#inner)),
+            None => (quote!(generated), quote!(#t)),
+        },
+        _ => abort!(func.sig, "AOC generator cannot return ()"),
+    };
+
+    quote! {
+        #func
+
+        pub(crate) fn #generator_func_name(timings: &mut crate::Timings) -> ::eyre::Result<::std::sync::Arc<#ty>> {
+            static GENERATED: ::std::sync::Mutex<Option<(Vec<u8>, ::std::sync::Arc<#ty>)>> =
+                ::std::sync::Mutex::new(None);
+
+            let start = ::std::time::Instant::now();
+            #load
+            timings.load = start.elapsed();
+
+            let start = ::std::time::Instant::now();
+            let mut cached = GENERATED.lock().unwrap_or_else(::std::sync::PoisonError::into_inner);
+            let generated = match cached.as_ref() {
+                Some((key, generated)) if *key == input => ::std::sync::Arc::clone(generated),
+                _ => {
+                    let generated = #func_name(#arg);
+                    let generated = ::std::sync::Arc::new(#output);
+                    *cached = Some((input, ::std::sync::Arc::clone(&generated)));
+
+                    generated
+                },
+            };
+            timings.generate = start.elapsed();
+
+            Ok(generated)
         }
     }.into()
 }
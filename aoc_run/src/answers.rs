@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use aoc_common::Part;
+use eyre::{eyre, WrapErr};
+
+/// Accepted answers for a single year, keyed by day, part, and version.
+///
+/// Answers are read from `data/answers/y{year}.toml` in the workspace, with one
+/// table per day:
+///
+/// ```toml
+/// [day01]
+/// A = "232"
+/// B = 1783
+///
+/// [day19]
+/// B = "195"
+///
+/// [day19.do_the_work]
+/// B = "207"
+/// ```
+///
+/// An answer for a part is expected from every version of its solution,
+/// unless the version has its own answer in a table named after it within
+/// the day.
+#[derive(Default)]
+pub(crate) struct Answers(BTreeMap<(usize, Part, Option<String>), String>);
+
+impl Answers {
+    fn path(year: usize) -> eyre::Result<PathBuf> {
+        Ok(aoc::input::workspace_root()?.join(format!("data/answers/y{year}.toml")))
+    }
+
+    /// Load the answers for `year`, or no answers if the file doesn't exist.
+    pub(crate) fn load(year: usize) -> eyre::Result<Self> {
+        let path = Self::path(year)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("unable to read {}", path.display()))?;
+        Self::parse(&contents)
+            .wrap_err_with(|| format!("unable to parse {}", path.display()))
+    }
+
+    fn parse(contents: &str) -> eyre::Result<Self> {
+        let table: toml::Table = contents.parse()?;
+        let mut answers = BTreeMap::new();
+
+        for (key, parts) in table {
+            let day: usize = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| eyre!("expected table named `dayNN`, found `{key}`"))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| eyre!("expected `{key}` to be a table"))?;
+
+            for (name, value) in parts {
+                match value {
+                    toml::Value::Table(versioned) => {
+                        for (part, answer) in versioned {
+                            let location = format!("{key}.{name}");
+                            let (part, answer) = Self::parse_answer(&location, part, answer)?;
+                            answers.insert((day, part, Some(name.clone())), answer);
+                        }
+                    },
+                    answer => {
+                        let (part, answer) = Self::parse_answer(&key, name, answer)?;
+                        answers.insert((day, part, None), answer);
+                    },
+                }
+            }
+        }
+
+        Ok(Self(answers))
+    }
+
+    /// Parse the answer `answer` for `part` in the table named `location`.
+    fn parse_answer(location: &str, part: &str, answer: &toml::Value) -> eyre::Result<(Part, String)> {
+        let part: Part = part
+            .parse()
+            .wrap_err_with(|| format!("invalid part `{part}` in `{location}`"))?;
+        let answer = match answer {
+            toml::Value::String(s) => s.clone(),
+            toml::Value::Integer(n) => n.to_string(),
+            v => return Err(eyre!("expected string or integer answer for {location} part {part}, found {}", v.type_str())),
+        };
+
+        Ok((part, answer))
+    }
+
+    /// Answer expected from `version` of the solution for `day` and `part`,
+    /// falling back to the answer for the part.
+    pub(crate) fn get(&self, day: usize, part: Part, version: Option<&str>) -> Option<&str> {
+        version
+            .and_then(|version| self.0.get(&(day, part, Some(version.to_string()))))
+            .or_else(|| self.0.get(&(day, part, None)))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("[day01]\nA = \"232\"\nB = 1783\n\n[day19]\nB = \"195\"\n").unwrap();
+        assert_eq!(answers.get(1, Part::A, None), Some("232"));
+        assert_eq!(answers.get(1, Part::B, None), Some("1783"));
+        assert_eq!(answers.get(19, Part::A, None), None);
+        assert_eq!(answers.get(19, Part::B, None), Some("195"));
+        assert_eq!(answers.get(19, Part::B, Some("alt")), Some("195"));
+
+        assert!(Answers::parse("[one]\nA = \"1\"").is_err());
+        assert!(Answers::parse("[day01]\nC = \"1\"").is_err());
+        assert!(Answers::parse("[day01]\nA = 1.5").is_err());
+        assert!(Answers::parse("[day01.alt]\nC = \"1\"").is_err());
+    }
+
+    #[test]
+    fn parse_version_answers() {
+        let answers = Answers::parse("[day19]\nB = \"195\"\n\n[day19.do_the_work]\nB = 207\n\n[day20.fast]\nA = \"1\"\n").unwrap();
+        assert_eq!(answers.get(19, Part::B, None), Some("195"));
+        assert_eq!(answers.get(19, Part::B, Some("do_the_work")), Some("207"));
+        assert_eq!(answers.get(19, Part::B, Some("other")), Some("195"));
+        assert_eq!(answers.get(19, Part::A, Some("do_the_work")), None);
+        assert_eq!(answers.get(20, Part::A, Some("fast")), Some("1"));
+        assert_eq!(answers.get(20, Part::A, None), None);
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::Duration;
+
+use crate::{pretty_duration, time_runner, to_duration};
+
+/// Settings for benchmarking a solution.
+#[derive(Clone, Copy)]
+pub(crate) struct Bench {
+    /// Number of timed runs.
+    pub(crate) runs: usize,
+    /// Number of untimed runs done before the timed runs.
+    pub(crate) warmup: usize,
+}
+
+/// Summary statistics over a set of timed runs.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct Stats {
+    pub(crate) runs: usize,
+    pub(crate) min: Duration,
+    pub(crate) median: Duration,
+    pub(crate) mean: Duration,
+    pub(crate) std_dev: Duration,
+    pub(crate) p95: Duration,
+}
+
+impl Stats {
+    /// Calculate statistics for `samples`, which must not be empty.
+    pub(crate) fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot calculate statistics without samples");
+
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.num_nanoseconds().unwrap_or(i64::MAX))
+            .collect::<Vec<_>>();
+        nanos.sort_unstable();
+
+        let n = nanos.len();
+        let median = if n % 2 == 0 {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2
+        } else {
+            nanos[n / 2]
+        };
+        let mean = nanos.iter().map(|&d| d as f64).sum::<f64>() / n as f64;
+        let variance = nanos
+            .iter()
+            .map(|&d| (d as f64 - mean).powi(2))
+            .sum::<f64>() / n as f64;
+        // Nearest-rank percentile
+        let p95 = nanos[((n as f64 * 0.95).ceil() as usize).clamp(1, n) - 1];
+
+        Self {
+            runs: n,
+            min: Duration::nanoseconds(nanos[0]),
+            median: Duration::nanoseconds(median),
+            mean: Duration::nanoseconds(mean.round() as i64),
+            std_dev: Duration::nanoseconds(variance.sqrt().round() as i64),
+            p95: Duration::nanoseconds(p95),
+        }
+    }
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} runs: min {}, median {}, mean {}, std dev {}, p95 {}",
+            self.runs,
+            pretty_duration(self.min),
+            pretty_duration(self.median),
+            pretty_duration(self.mean),
+            pretty_duration(self.std_dev),
+            pretty_duration(self.p95),
+        )
+    }
+}
+
+/// Run `runner` `bench.warmup` times, then time it `bench.runs` times.
+///
+/// Each sample is the total time of a run, including loading the input.
+///
+/// Stops at the first run that fails.
+pub(crate) fn bench<F>(runner: F, bench: Bench) -> eyre::Result<Stats>
+where F: Fn(&mut aoc::Timings) -> eyre::Result<String>
+{
+    for _ in 0..bench.warmup {
+        time_runner(&runner).0?;
+    }
+
+    let mut samples = Vec::with_capacity(bench.runs);
+    for _ in 0..bench.runs {
+        let (result, timings) = time_runner(&runner);
+        result?;
+        samples.push(to_duration(timings.total()));
+    }
+
+    Ok(Stats::from_samples(&samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples() {
+        let samples = [5, 1, 4, 2, 3]
+            .map(Duration::microseconds);
+        assert_eq!(Stats::from_samples(&samples), Stats {
+            runs: 5,
+            min: Duration::microseconds(1),
+            median: Duration::microseconds(3),
+            mean: Duration::microseconds(3),
+            std_dev: Duration::nanoseconds(1414),
+            p95: Duration::microseconds(5),
+        });
+
+        let samples = (1..=20)
+            .map(Duration::milliseconds)
+            .collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(stats.median, Duration::microseconds(10_500));
+        assert_eq!(stats.p95, Duration::milliseconds(19));
+    }
+}
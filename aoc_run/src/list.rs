@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use aoc_common::Part;
+use itertools::Itertools;
+
+/// Registered parts and alternate versions of a single day.
+#[derive(Default)]
+struct Day {
+    a: bool,
+    b: bool,
+    versions: Vec<(Part, String)>,
+}
+
+impl Day {
+    fn stars(&self) -> usize {
+        usize::from(self.a) + usize::from(self.b)
+    }
+}
+
+/// Every registered solution, grouped by year and day.
+#[derive(Default)]
+pub(crate) struct Inventory(BTreeMap<usize, BTreeMap<usize, Day>>);
+
+impl Inventory {
+    pub(crate) fn insert(&mut self, year: usize, day: usize, part: Part, version: Option<String>) {
+        let entry = self.0
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default();
+
+        match (part, version) {
+            (Part::A, None) => entry.a = true,
+            (Part::B, None) => entry.b = true,
+            (part, Some(version)) => entry.versions.push((part, version)),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Calendar of stars for each year, followed by any days that only have
+    /// part A, and any alternate versions.
+    pub(crate) fn render(&self) -> String {
+        let mut lines = Vec::new();
+
+        for (year, days) in &self.0 {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+
+            let stars = days.values().map(Day::stars).sum::<usize>();
+            lines.push(format!("{year}: {stars}/50 stars"));
+
+            for week in &(1..=25).chunks(5) {
+                let cells = week
+                    .map(|day| {
+                        let stars = days.get(&day).map_or(0, Day::stars);
+                        format!("{day:02} {:<2}", "*".repeat(stars))
+                    })
+                    .join("  ");
+                lines.push(format!("  {}", cells.trim_end()));
+            }
+
+            let part_a_only = days
+                .iter()
+                .filter(|(_, d)| d.a && !d.b)
+                .map(|(day, _)| format!("{day:02}"))
+                .collect::<Vec<_>>();
+            if !part_a_only.is_empty() {
+                lines.push(format!("  Part A only: {}", part_a_only.join(", ")));
+            }
+
+            let versions = days
+                .iter()
+                .flat_map(|(day, d)| d.versions.iter().map(move |(part, version)| (day, part, version)))
+                .sorted()
+                .collect::<Vec<_>>();
+            if !versions.is_empty() {
+                lines.push(String::from("  Alternate versions:"));
+                for (day, part, version) in versions {
+                    lines.push(format!("    {day:02} part {part}: {version}"));
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_inventory() {
+        let mut inventory = Inventory::default();
+        inventory.insert(2015, 1, Part::A, None);
+        inventory.insert(2015, 1, Part::B, None);
+        inventory.insert(2015, 7, Part::A, None);
+        inventory.insert(2015, 19, Part::B, Some(String::from("do_the_work")));
+        inventory.insert(2015, 19, Part::A, None);
+        inventory.insert(2015, 19, Part::B, None);
+        inventory.insert(2016, 25, Part::A, None);
+
+        assert_eq!(inventory.render(), [
+            "2015: 5/50 stars",
+            "  01 **  02     03     04     05",
+            "  06     07 *   08     09     10",
+            "  11     12     13     14     15",
+            "  16     17     18     19 **  20",
+            "  21     22     23     24     25",
+            "  Part A only: 07",
+            "  Alternate versions:",
+            "    19 part B: do_the_work",
+            "",
+            "2016: 1/50 stars",
+            "  01     02     03     04     05",
+            "  06     07     08     09     10",
+            "  11     12     13     14     15",
+            "  16     17     18     19     20",
+            "  21     22     23     24     25 *",
+            "  Part A only: 25",
+        ].join("\n"));
+    }
+}
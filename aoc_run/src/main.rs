@@ -1,17 +1,29 @@
+use std::collections::{btree_map::Entry, BTreeMap};
 use std::fs;
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
 
 use askama::Template;
 use chrono::Duration;
 use clap::{crate_version, Parser};
+use eyre::WrapErr;
 use itertools::Itertools;
 
+mod answers;
+use answers::Answers;
+mod bench;
+use bench::Bench;
+mod list;
+use list::Inventory;
 mod parser;
 use parser::*;
+mod report;
+use report::Record;
 mod template;
 use template::AocTemplate;
 
-fn pretty_duration(duration: Duration) -> String {
+pub(crate) fn pretty_duration(duration: Duration) -> String {
     if duration < Duration::microseconds(1) {
         format!("{} ns", duration.num_nanoseconds().unwrap())
     } else if duration < Duration::milliseconds(1) {
@@ -37,6 +49,10 @@ fn main() -> eyre::Result<()> {
 
     let cli = Cli::parse();
 
+    if let Some(dir) = &cli.input_dir {
+        aoc::input::set_input_dir(dir);
+    }
+
     match &cli.command {
         Commands::Create(args) => {
             let CreateArgs {
@@ -51,40 +67,24 @@ fn main() -> eyre::Result<()> {
                 return Ok(());
             }
 
-            let current_dir = std::env::current_dir().unwrap();
-            let input_dir_name = format!(
-                "{}/data/input/y{year}",
-                current_dir.display(),
-            );
-            let input_dir = Path::new(&input_dir_name);
+            let input_file = aoc::input::input_path(year, day)?;
+            let input_dir = input_file.parent().unwrap();
 
             if !input_dir.exists() {
                 fs::create_dir_all(input_dir)?;
             }
 
-            let input_file = input_dir.join(format!(
-                "{}/day{day:02}.txt",
-                input_dir.display(),
-            ));
-
             if !input_file.exists() {
-                fs::write(input_file, "")?;
+                fs::write(&input_file, "")?;
             }
 
-            let solution_dir_name = format!(
-                "{}/aoc/src/solutions/y{year}",
-                current_dir.display(),
-            );
-            let solution_dir = Path::new(&solution_dir_name);
+            let solution_dir = aoc::input::workspace_root()?.join(format!("aoc/src/solutions/y{year}"));
 
             if !solution_dir.exists() {
-                fs::create_dir_all(solution_dir)?;
+                fs::create_dir_all(&solution_dir)?;
             }
 
-            let solution_file = solution_dir.join(format!(
-                "{}/day{day:02}.rs",
-                solution_dir.display(),
-            ));
+            let solution_file = solution_dir.join(format!("day{day:02}.rs"));
 
             if !solution_file.exists() {
                 let (input_name, input_type) =
@@ -100,12 +100,19 @@ fn main() -> eyre::Result<()> {
             let RunArgs {
                 year,
                 day,
+                all,
                 run_part_a,
                 run_part_b,
                 version: _,
+                input: _,
+                examples,
+                format,
+                bench,
+                warmup,
                 show_version,
             } = *args;
             let version = args.version.clone();
+            let bench = bench.map(|runs| Bench { runs: runs.get(), warmup });
 
             if show_version {
                 println!("aoc v{}", crate_version!());
@@ -114,11 +121,93 @@ fn main() -> eyre::Result<()> {
 
             aoc::register_runners();
 
+            if let Some(input) = &args.input {
+                aoc::input::set_input(read_input(input)?);
+            }
+
+            if examples {
+                let year = match all {
+                    true => year,
+                    false => Some(year.unwrap_or(DEFAULT_YEAR)),
+                };
+                let runners = aoc::get_runners(year)
+                    .into_iter()
+                    .filter(|runner| all || (
+                        Some(runner.day) == day
+                        && runner.version == version
+                        && match runner.part {
+                            aoc_common::Part::A => run_part_a,
+                            aoc_common::Part::B => run_part_b,
+                        }
+                    ))
+                    .collect();
+
+                return run_examples(runners);
+            }
+
+            if all {
+                let records = aoc::get_runners(year)
+                    .into_iter()
+                    .map(|runner| {
+                        let (result, timings) = time_runner(|timings| (runner.runner)(timings));
+
+                        Record {
+                            year: runner.year,
+                            day: runner.day,
+                            part: runner.part,
+                            version: runner.version,
+                            result,
+                            timings,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                println!("{}", report::render(format, &records));
+                return Ok(());
+            }
+
+            let year = year.unwrap_or(DEFAULT_YEAR);
+            let day = day.expect("day is required unless running all solutions");
+
+            if format != OutputFormat::Pretty {
+                let mut records = Vec::new();
+                if run_part_a {
+                    records.push(run_record(year, day, aoc_common::Part::A, version.clone()));
+                }
+                if run_part_b {
+                    records.push(run_record(year, day, aoc_common::Part::B, version.clone()));
+                }
+
+                println!("{}", report::render(format, &records));
+                return Ok(());
+            }
+
             if run_part_a {
-                run(year, day, aoc_common::Part::A, version.clone());
+                run(year, day, aoc_common::Part::A, version.clone(), bench);
             }
             if run_part_b {
-                run(year, day, aoc_common::Part::B, version.clone());
+                run(year, day, aoc_common::Part::B, version.clone(), bench);
+            }
+
+            Ok(())
+        },
+        Commands::Verify(args) => {
+            aoc::register_runners();
+
+            verify(args.year, args.allow_missing)
+        },
+        Commands::List(args) => {
+            aoc::register_runners();
+
+            let mut inventory = Inventory::default();
+            for runner in aoc::get_runners(args.year) {
+                inventory.insert(runner.year, runner.day, runner.part, runner.version);
+            }
+
+            match args.year {
+                Some(year) if inventory.is_empty() => println!("No solutions registered for {year}"),
+                None if inventory.is_empty() => println!("No solutions registered"),
+                _ => println!("{}", inventory.render()),
             }
 
             Ok(())
@@ -127,13 +216,52 @@ fn main() -> eyre::Result<()> {
 
 }
 
-fn run(year: usize, day: usize, part: aoc_common::Part, version: Option<String>) {
+/// Read the puzzle input at `path`, or standard input if `path` is `-`.
+fn read_input(path: &Path) -> eyre::Result<Vec<u8>> {
+    if path == Path::new("-") {
+        let mut input = Vec::new();
+        io::stdin()
+            .read_to_end(&mut input)
+            .wrap_err("unable to read input from standard input")?;
+
+        Ok(input)
+    } else {
+        fs::read(path).wrap_err_with(|| format!("unable to read input from {}", path.display()))
+    }
+}
+
+/// Time a single call of `runner`, turning a panic into an error so that one
+/// unfinished solution doesn't take down a whole batch of runs.
+pub(crate) fn time_runner<F>(runner: F) -> (eyre::Result<String>, aoc::Timings)
+where F: Fn(&mut aoc::Timings) -> eyre::Result<String>
+{
+    let mut timings = aoc::Timings::default();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| runner(&mut timings)))
+        .unwrap_or_else(|_| Err(eyre::eyre!("solution panicked")));
+
+    (result, timings)
+}
+
+pub(crate) fn to_duration(duration: std::time::Duration) -> Duration {
+    Duration::from_std(duration).unwrap_or(Duration::MAX)
+}
+
+/// Format `timings` as the total time, followed by the time taken by each phase.
+pub(crate) fn pretty_timings(timings: &aoc::Timings) -> String {
+    format!(
+        "{}; load {}, generate {}, solve {}",
+        pretty_duration(to_duration(timings.total())),
+        pretty_duration(to_duration(timings.load)),
+        pretty_duration(to_duration(timings.generate)),
+        pretty_duration(to_duration(timings.solve)),
+    )
+}
+
+fn run(year: usize, day: usize, part: aoc_common::Part, version: Option<String>, bench: Option<Bench>) {
     match aoc::get_runner(year, day, part, version.clone()) {
         Ok(runner) => {
-            let before = chrono::Utc::now();
-            let result = runner();
-            let after = chrono::Utc::now();
-            let elapsed = format!(" ({})", pretty_duration(after - before));
+            let (result, timings) = time_runner(|timings| runner(timings));
+            let elapsed = format!(" ({})", pretty_timings(&timings));
             let header = format!(
                 r#"Solution for {year}/{day:02} part {part}{}:"#,
                 version.clone().map(|v| format!(r#" (version "{v}")"#)).unwrap_or_default(),
@@ -143,7 +271,17 @@ fn run(year: usize, day: usize, part: aoc_common::Part, version: Option<String>)
             match result {
                 Ok(v) => {
                     let result = v.lines().join(&sep);
-                    println!("{header} {result}{elapsed}");
+
+                    match bench {
+                        None => println!("{header} {result}{elapsed}"),
+                        Some(b) => {
+                            println!("{header} {result}");
+                            match bench::bench(|timings| runner(timings), b) {
+                                Ok(stats) => println!("{}{stats} ({} warm-up)", &sep[1..], b.warmup),
+                                Err(e) => eprintln!("<error while benchmarking: {}>", e),
+                            }
+                        },
+                    }
                 },
                 Err(e) => {
                     eprintln!("<error: {}>", e);
@@ -160,3 +298,130 @@ fn run(year: usize, day: usize, part: aoc_common::Part, version: Option<String>)
         },
     }
 }
+
+/// Run a solution once, without printing anything.
+fn run_record(year: usize, day: usize, part: aoc_common::Part, version: Option<String>) -> Record {
+    let (result, timings) = match aoc::get_runner(year, day, part, version.clone()) {
+        Ok(runner) => time_runner(|timings| runner(timings)),
+        Err(e) => (Err(e.into()), aoc::Timings::default()),
+    };
+
+    Record {
+        year,
+        day,
+        part,
+        version,
+        result,
+        timings,
+    }
+}
+
+/// Name of `runner`'s solution, for reporting.
+fn solution_name(runner: &aoc::RegisteredRunner) -> String {
+    format!(
+        "{}/{:02} part {}{}",
+        runner.year,
+        runner.day,
+        runner.part,
+        runner.version.as_ref().map(|v| format!(r#" (version "{v}")"#)).unwrap_or_default(),
+    )
+}
+
+/// Run each of `runners` on its examples, and compare each answer with the
+/// expected one.
+///
+/// Fails if any solution gives a different answer, or returns an error.
+fn run_examples(runners: Vec<aoc::RegisteredRunner>) -> eyre::Result<()> {
+    let (mut passed, mut failed, mut without) = (0, 0, 0);
+
+    for runner in runners {
+        let name = solution_name(&runner);
+        let examples = aoc::get_examples(runner.year, runner.day, runner.part, runner.version.clone());
+        if examples.is_empty() {
+            without += 1;
+            continue;
+        }
+
+        for example in examples {
+            let name = format!("{name} example {}", example.name);
+            let result = aoc::input::with_input(example.input.as_bytes(), || {
+                time_runner(|timings| (runner.runner)(timings)).0
+            });
+
+            match result {
+                Ok(actual) if actual.trim() == example.expected.trim() => {
+                    println!("PASS    {name}");
+                    passed += 1;
+                },
+                Ok(actual) => {
+                    println!("FAIL    {name}: expected {}, got {}", example.expected.trim(), actual.trim());
+                    failed += 1;
+                },
+                Err(e) => {
+                    println!("FAIL    {name}: <error: {}>", e);
+                    failed += 1;
+                },
+            }
+        }
+    }
+
+    println!();
+    println!("{passed} passed, {failed} failed, {without} solution(s) without examples");
+
+    if failed > 0 {
+        eyre::bail!("{failed} example(s) failed");
+    }
+
+    Ok(())
+}
+
+/// Run every registered solution for `year` (or every year), and compare each
+/// answer with the one stored in `data/answers`.
+///
+/// Fails if any solution gives a different answer, returns an error, or has
+/// no stored answer (unless `allow_missing` is set).
+fn verify(year: Option<usize>, allow_missing: bool) -> eyre::Result<()> {
+    let mut answers: BTreeMap<usize, Answers> = BTreeMap::new();
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for runner in aoc::get_runners(year) {
+        let answers = match answers.entry(runner.year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Answers::load(runner.year)?),
+        };
+        let name = solution_name(&runner);
+
+        let Some(expected) = answers.get(runner.day, runner.part, runner.version.as_deref()) else {
+            println!("MISSING {name}");
+            missing += 1;
+            continue;
+        };
+
+        match time_runner(|timings| (runner.runner)(timings)).0 {
+            Ok(actual) if actual.trim() == expected.trim() => {
+                println!("PASS    {name}");
+                passed += 1;
+            },
+            Ok(actual) => {
+                println!("FAIL    {name}: expected {}, got {}", expected.trim(), actual.trim());
+                failed += 1;
+            },
+            Err(e) => {
+                println!("FAIL    {name}: <error: {}>", e);
+                failed += 1;
+            },
+        }
+    }
+
+    println!();
+    println!("{passed} passed, {failed} failed, {missing} missing");
+
+    if failed > 0 {
+        eyre::bail!("{failed} solution(s) failed verification");
+    }
+    if missing > 0 && !allow_missing {
+        eyre::bail!("{missing} solution(s) have no stored answer (pass --allow-missing to skip them)");
+    }
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
+use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
+use std::path::PathBuf;
 
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
@@ -8,6 +10,10 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
 pub(crate) struct Cli {
     #[command(subcommand)]
     pub(crate) command: Commands,
+
+    #[arg(long, global = true, value_name = "DIR")]
+    /// Directory of puzzle inputs, used unless AOC_INPUT_DIR is set (defaults to `data/input` in the workspace)
+    pub(crate) input_dir: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -16,11 +22,15 @@ pub(crate) enum Commands {
     Create(CreateArgs),
     /// Run solution
     Run(RunArgs),
+    /// Check solutions against their stored answers
+    Verify(VerifyArgs),
+    /// List registered solutions
+    List(ListArgs),
 }
 
 #[derive(Args)]
 pub(crate) struct CreateArgs {
-    #[arg(short, long, default_value_t = 2023, value_parser = year_in_range)]
+    #[arg(short, long, default_value_t = DEFAULT_YEAR, value_parser = year_in_range)]
     /// Number in range [2015, 2023]
     pub(crate) year: usize,
 
@@ -74,13 +84,17 @@ impl InputType {
 
 #[derive(Args)]
 pub(crate) struct RunArgs {
-    #[arg(short, long, default_value_t = 2023, value_parser = year_in_range)]
-    /// Number in range [2015, 2023]
-    pub(crate) year: usize,
+    #[arg(short, long, value_parser = year_in_range)]
+    /// Number in range [2015, 2023] (defaults to 2023, or to every year with `--all`)
+    pub(crate) year: Option<usize>,
 
-    #[arg(short, long, value_parser = day_in_range)]
+    #[arg(short, long, value_parser = day_in_range, required_unless_present = "all")]
     /// Number in range [1, 25]
-    pub(crate) day: usize,
+    pub(crate) day: Option<usize>,
+
+    #[arg(long, conflicts_with_all = ["day", "run_part_a", "run_part_b", "version"])]
+    /// Run every registered solution for the given year, or for every year
+    pub(crate) all: bool,
 
     #[arg(short = 'a')]
     /// Run solution or tests for part A
@@ -94,10 +108,63 @@ pub(crate) struct RunArgs {
     /// Alternate version of given solution to run
     pub(crate) version: Option<String>,
 
+    #[arg(short, long, value_name = "FILE", conflicts_with = "all")]
+    /// Run the solution on FILE, or on standard input if FILE is `-`, instead of the stored puzzle input
+    pub(crate) input: Option<PathBuf>,
+
+    #[arg(long, conflicts_with_all = ["input", "format", "bench"])]
+    /// Run the solution on its examples, and check the answers
+    pub(crate) examples: bool,
+
+    #[arg(long, value_enum, default_value_t = OutputFormat::Pretty)]
+    /// Format of the results
+    pub(crate) format: OutputFormat,
+
+    #[arg(long, value_name = "N", conflicts_with_all = ["all", "format"])]
+    /// Benchmark the solution over N timed runs
+    pub(crate) bench: Option<NonZeroUsize>,
+
+    #[arg(long, value_name = "N", default_value_t = 3, requires = "bench")]
+    /// Number of untimed runs before benchmarking
+    pub(crate) warmup: usize,
+
     #[arg(short = 'V')]
     pub(crate) show_version: bool,
 }
 
+#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// human-readable results
+    Pretty,
+    /// JSON array with one object per result
+    Json,
+    /// CSV with a header row
+    Csv,
+    /// Markdown table
+    Markdown,
+}
+
+#[derive(Args)]
+pub(crate) struct VerifyArgs {
+    #[arg(short, long, value_parser = year_in_range)]
+    /// Number in range [2015, 2023] (defaults to every year)
+    pub(crate) year: Option<usize>,
+
+    #[arg(long)]
+    /// Report solutions without a stored answer, rather than failing
+    pub(crate) allow_missing: bool,
+}
+
+#[derive(Args)]
+pub(crate) struct ListArgs {
+    #[arg(short, long, value_parser = year_in_range)]
+    /// Number in range [2015, 2023] (defaults to every year)
+    pub(crate) year: Option<usize>,
+}
+
+/// Year used when a command is not given one.
+pub(crate) const DEFAULT_YEAR: usize = 2023;
+
 /// Valid years.
 ///
 /// Will need to update upper bound for each new Advent of Code event.
@@ -0,0 +1,248 @@
+use aoc::common::{Json, Map};
+use aoc_common::Part;
+use itertools::Itertools;
+
+use crate::{pretty_duration, pretty_timings, to_duration};
+use crate::parser::OutputFormat;
+
+/// Result of a single run of a solution.
+pub(crate) struct Record {
+    pub(crate) year: usize,
+    pub(crate) day: usize,
+    pub(crate) part: Part,
+    pub(crate) version: Option<String>,
+    pub(crate) result: eyre::Result<String>,
+    pub(crate) timings: aoc::Timings,
+}
+
+impl Record {
+    /// Messages of the error, and every error that caused it.
+    fn error_chain(&self) -> Option<Vec<String>> {
+        self.result
+            .as_ref()
+            .err()
+            .map(|e| e.chain().map(|cause| cause.to_string()).collect())
+    }
+}
+
+/// Records as a JSON array, with one object per record.
+pub(crate) fn json(records: &[Record]) -> String {
+    let objects = records
+        .iter()
+        .map(|record| {
+            let mut object = Map::new();
+            object.insert("year", record.year);
+            object.insert("day", record.day);
+            object.insert("part", record.part.to_string());
+            object.insert("version", record.version.as_deref());
+            object.insert("success", record.result.is_ok());
+            object.insert("answer", record.result.as_deref().ok());
+            object.insert("error", record.error_chain());
+            object.insert("duration_ns", record.timings.total().as_nanos());
+            object.insert("load_ns", record.timings.load.as_nanos());
+            object.insert("generate_ns", record.timings.generate.as_nanos());
+            object.insert("solve_ns", record.timings.solve.as_nanos());
+
+            Json::Object(object)
+        })
+        .collect();
+
+    Json::Array(objects).to_string()
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!(r#""{}""#, s.replace('"', r#""""#))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Records as CSV, with a header row. Error cause chains are joined with `: `.
+pub(crate) fn csv(records: &[Record]) -> String {
+    let mut lines = vec![String::from(
+        "year,day,part,version,success,answer,error,duration_ns,load_ns,generate_ns,solve_ns",
+    )];
+
+    for record in records {
+        lines.push([
+            record.year.to_string(),
+            record.day.to_string(),
+            record.part.to_string(),
+            csv_field(record.version.as_deref().unwrap_or_default()),
+            record.result.is_ok().to_string(),
+            csv_field(record.result.as_deref().unwrap_or_default()),
+            csv_field(&record.error_chain().unwrap_or_default().join(": ")),
+            record.timings.total().as_nanos().to_string(),
+            record.timings.load.as_nanos().to_string(),
+            record.timings.generate.as_nanos().to_string(),
+            record.timings.solve.as_nanos().to_string(),
+        ].join(","));
+    }
+
+    lines.join("\n")
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', r"\|")
+        .lines()
+        .join("<br>")
+}
+
+/// Records as a Markdown table, with human-readable timings.
+pub(crate) fn markdown(records: &[Record]) -> String {
+    let mut lines = vec![
+        String::from("| Year | Day | Part | Version | Answer | Time |"),
+        String::from("| ---: | --: | :--: | ------- | ------ | ---: |"),
+    ];
+
+    for record in records {
+        let answer = match &record.result {
+            Ok(answer) => markdown_cell(answer),
+            Err(e) => markdown_cell(&format!("error: {e}")),
+        };
+
+        lines.push(format!(
+            "| {} | {:02} | {} | {} | {} | {} |",
+            record.year,
+            record.day,
+            record.part,
+            markdown_cell(record.version.as_deref().unwrap_or_default()),
+            answer,
+            pretty_duration(to_duration(record.timings.total())),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// Records as a table of answers and timings, followed by a summary line.
+pub(crate) fn table(records: &[Record]) -> String {
+    let header = ["Year", "Day", "Part", "Version", "Answer", "Load", "Generate", "Solve", "Total"];
+    let mut rows = Vec::new();
+    let mut total = aoc::Timings::default();
+
+    for record in records {
+        let timings = record.timings;
+        total.load += timings.load;
+        total.generate += timings.generate;
+        total.solve += timings.solve;
+
+        let answer = match &record.result {
+            Ok(v) => v.lines().join(" "),
+            Err(e) => format!("<error: {}>", e),
+        };
+
+        rows.push([
+            record.year.to_string(),
+            format!("{:02}", record.day),
+            record.part.to_string(),
+            record.version.clone().unwrap_or_default(),
+            answer,
+            pretty_duration(to_duration(timings.load)),
+            pretty_duration(to_duration(timings.generate)),
+            pretty_duration(to_duration(timings.solve)),
+            pretty_duration(to_duration(timings.total())),
+        ]);
+    }
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[&str]| {
+        row.iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let rule = widths.map(|width| "-".repeat(width));
+    let mut lines = vec![
+        format_row(&header),
+        format_row(&rule.each_ref().map(String::as_str)),
+    ];
+    for row in &rows {
+        lines.push(format_row(&row.each_ref().map(String::as_str)));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("{} solutions run in {}", rows.len(), pretty_timings(&total)));
+
+    lines.join("\n")
+}
+
+/// Records in the given format.
+pub(crate) fn render(format: OutputFormat, records: &[Record]) -> String {
+    match format {
+        OutputFormat::Pretty => table(records),
+        OutputFormat::Json => json(records),
+        OutputFormat::Csv => csv(records),
+        OutputFormat::Markdown => markdown(records),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2015,
+                day: 1,
+                part: Part::A,
+                version: None,
+                result: Ok(String::from("232")),
+                timings: aoc::Timings {
+                    load: Duration::from_nanos(10),
+                    generate: Duration::from_nanos(20),
+                    solve: Duration::from_nanos(30),
+                },
+            },
+            Record {
+                year: 2015,
+                day: 19,
+                part: Part::B,
+                version: Some(String::from("a, \"b\"")),
+                result: Err(eyre::eyre!("inner").wrap_err("outer | line")),
+                timings: aoc::Timings::default(),
+            },
+        ]
+    }
+
+    #[test]
+    fn format_json() {
+        assert_eq!(json(&records()), concat!(
+            r#"[{"year":2015,"day":1,"part":"A","version":null,"success":true,"answer":"232","error":null,"duration_ns":60,"load_ns":10,"generate_ns":20,"solve_ns":30},"#,
+            r#"{"year":2015,"day":19,"part":"B","version":"a, \"b\"","success":false,"answer":null,"error":["outer | line","inner"],"duration_ns":0,"load_ns":0,"generate_ns":0,"solve_ns":0}]"#,
+        ));
+        assert_eq!(Json::from("\u{1}\ttab").to_string(), r#""\u0001\ttab""#);
+    }
+
+    #[test]
+    fn format_csv() {
+        assert_eq!(csv(&records()), [
+            "year,day,part,version,success,answer,error,duration_ns,load_ns,generate_ns,solve_ns",
+            "2015,1,A,,true,232,,60,10,20,30",
+            r#"2015,19,B,"a, ""b""",false,,outer | line: inner,0,0,0,0"#,
+        ].join("\n"));
+    }
+
+    #[test]
+    fn format_markdown() {
+        assert_eq!(markdown(&records()), [
+            "| Year | Day | Part | Version | Answer | Time |",
+            "| ---: | --: | :--: | ------- | ------ | ---: |",
+            "| 2015 | 01 | A |  | 232 | 60 ns |",
+            r#"| 2015 | 19 | B | a, "b" | error: outer \| line | 0 ns |"#,
+        ].join("\n"));
+    }
+}
@@ -0,0 +1,18 @@
+#![no_main]
+
+use aoc::common::{Json, Tokenizer};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &[u8]| {
+    let parsed = Json::parse(input);
+    let tokenized = Tokenizer::new(input).collect::<Result<Vec<_>, _>>();
+    assert_eq!(parsed.is_ok(), tokenized.is_ok());
+
+    match parsed {
+        Ok(json) => {
+            assert_eq!(Json::parse(json.to_string().as_bytes()).as_ref(), Ok(&json));
+            assert_eq!(Json::parse(json.to_string_pretty(2).as_bytes()), Ok(json));
+        },
+        Err(error) => assert!(error.position().offset <= input.len()),
+    }
+});